@@ -23,9 +23,34 @@ pub enum DefType {
 	RefCell(DefRefCell),
 	Mutex(DefMutex),
 	RwLock(DefRwLock),
+	UnsafeCell(DefUnsafeCell),
+	OnceCell(DefOnceCell),
+	OnceLock(DefOnceLock),
+	Atomic(DefAtomic),
+	Map(DefMap),
+	Set(DefSet),
+	Seq(DefSeq),
+	InlineVec(DefInlineVec),
+	InlineString(DefInlineString),
 	Rc(DefRc),
 	Arc(DefArc),
+	RcWeak(DefRcWeak),
+	ArcWeak(DefArcWeak),
 	Result(DefResult),
+	Cow(DefCow),
+	Transparent(DefTransparent),
+	MaybeUninit(DefMaybeUninit),
+	Duration(DefDuration),
+	Opaque(DefOpaque),
+	Tuple(DefTuple),
+	Array(DefArray),
+	Slice(DefSlice),
+	SliceRef(DefSliceRef),
+	Ref(DefRef),
+	Ptr(DefPtr),
+	FnPtr(DefFnPtr),
+	Union(DefUnion),
+	Dyn(DefDyn),
 }
 
 macro_rules! getter {
@@ -49,9 +74,34 @@ macro_rules! getter {
 				DefType::RefCell(DefRefCell { $field, .. }) => $out,
 				DefType::Mutex(DefMutex { $field, .. }) => $out,
 				DefType::RwLock(DefRwLock { $field, .. }) => $out,
+				DefType::UnsafeCell(DefUnsafeCell { $field, .. }) => $out,
+				DefType::OnceCell(DefOnceCell { $field, .. }) => $out,
+				DefType::OnceLock(DefOnceLock { $field, .. }) => $out,
+				DefType::Atomic(DefAtomic { $field, .. }) => $out,
+				DefType::Map(DefMap { $field, .. }) => $out,
+				DefType::Set(DefSet { $field, .. }) => $out,
+				DefType::Seq(DefSeq { $field, .. }) => $out,
+				DefType::InlineVec(DefInlineVec { $field, .. }) => $out,
+				DefType::InlineString(DefInlineString { $field, .. }) => $out,
 				DefType::Rc(DefRc { $field, .. }) => $out,
 				DefType::Arc(DefArc { $field, .. }) => $out,
+				DefType::RcWeak(DefRcWeak { $field, .. }) => $out,
+				DefType::ArcWeak(DefArcWeak { $field, .. }) => $out,
 				DefType::Result(DefResult { $field, .. }) => $out,
+				DefType::Cow(DefCow { $field, .. }) => $out,
+				DefType::Transparent(DefTransparent { $field, .. }) => $out,
+				DefType::MaybeUninit(DefMaybeUninit { $field, .. }) => $out,
+				DefType::Duration(DefDuration { $field, .. }) => $out,
+				DefType::Opaque(DefOpaque { $field, .. }) => $unsized_unaligned,
+				DefType::Tuple(DefTuple { $field, .. }) => $out,
+				DefType::Array(DefArray { $field, .. }) => $out,
+				DefType::Slice(DefSlice { $field, .. }) => $unsized_out,
+				DefType::SliceRef(DefSliceRef { $field, .. }) => $out,
+				DefType::Ref(DefRef { $field, .. }) => $out,
+				DefType::Ptr(DefPtr { $field, .. }) => $out,
+				DefType::FnPtr(DefFnPtr { $field, .. }) => $out,
+				DefType::Union(DefUnion { $field, .. }) => $out,
+				DefType::Dyn(DefDyn { $field, .. }) => $unsized_unaligned,
 			}
 		}
 	};
@@ -115,11 +165,76 @@ impl DefType {
 
 	to_methods!(RwLock, DefRwLock, into_rw_lock, to_rw_lock);
 
+	to_methods!(UnsafeCell, DefUnsafeCell, into_unsafe_cell, to_unsafe_cell);
+
+	to_methods!(OnceCell, DefOnceCell, into_once_cell, to_once_cell);
+
+	to_methods!(OnceLock, DefOnceLock, into_once_lock, to_once_lock);
+
+	to_methods!(Atomic, DefAtomic, into_atomic, to_atomic);
+
+	to_methods!(Map, DefMap, into_map, to_map);
+
+	to_methods!(Set, DefSet, into_set, to_set);
+
+	to_methods!(Seq, DefSeq, into_seq, to_seq);
+
+	to_methods!(InlineVec, DefInlineVec, into_inline_vec, to_inline_vec);
+
+	to_methods!(
+		InlineString,
+		DefInlineString,
+		into_inline_string,
+		to_inline_string
+	);
+
 	to_methods!(Rc, DefRc, into_rc, to_rc);
 
 	to_methods!(Arc, DefArc, into_arc, to_arc);
 
+	to_methods!(RcWeak, DefRcWeak, into_rc_weak, to_rc_weak);
+
+	to_methods!(ArcWeak, DefArcWeak, into_arc_weak, to_arc_weak);
+
 	to_methods!(Result, DefResult, into_result, to_result);
+
+	to_methods!(Cow, DefCow, into_cow, to_cow);
+
+	to_methods!(
+		Transparent,
+		DefTransparent,
+		into_transparent,
+		to_transparent
+	);
+
+	to_methods!(
+		MaybeUninit,
+		DefMaybeUninit,
+		into_maybe_uninit,
+		to_maybe_uninit
+	);
+
+	to_methods!(Duration, DefDuration, into_duration, to_duration);
+
+	to_methods!(Opaque, DefOpaque, into_opaque, to_opaque);
+
+	to_methods!(Tuple, DefTuple, into_tuple, to_tuple);
+
+	to_methods!(Array, DefArray, into_array, to_array);
+
+	to_methods!(Slice, DefSlice, into_slice, to_slice);
+
+	to_methods!(SliceRef, DefSliceRef, into_slice_ref, to_slice_ref);
+
+	to_methods!(Ref, DefRef, into_ref, to_ref);
+
+	to_methods!(Ptr, DefPtr, into_ptr, to_ptr);
+
+	to_methods!(FnPtr, DefFnPtr, into_fn_ptr, to_fn_ptr);
+
+	to_methods!(Union, DefUnion, into_union, to_union);
+
+	to_methods!(Dyn, DefDyn, into_dyn, to_dyn);
 }
 
 macro_rules! def {
@@ -139,6 +254,27 @@ pub struct DefPrimitive {
 	pub name: String,
 	pub size: usize,
 	pub align: usize,
+	pub kind: DefPrimitiveKind,
+	// Valid bit patterns, read as an unsigned integer. Ranges are inclusive.
+	// Empty if type is uninhabited (`!`).
+	pub valid_ranges: Vec<DefValidRange>,
+}
+
+#[apply(def)]
+pub enum DefPrimitiveKind {
+	UnsignedInt,
+	SignedInt,
+	Float,
+	Bool,
+	Char,
+	Unit,
+	Never,
+}
+
+#[apply(def)]
+pub struct DefValidRange {
+	pub start: u128,
+	pub end: u128,
 }
 
 #[apply(def)]
@@ -150,6 +286,7 @@ pub struct DefStruct {
 	pub fields: Vec<DefStructField>,
 	pub tag: Option<String>,
 	pub transparent: bool,
+	pub repr: DefRepr,
 }
 
 #[apply(def)]
@@ -170,15 +307,70 @@ pub struct DefEnum {
 	pub align: usize,
 	pub variants: Vec<DefEnumVariant>,
 	pub tag: DefEnumTag,
+	pub repr: DefRepr,
+	// `None` if enum has no tag, or position of tag cannot be determined
+	pub tag_layout: Option<DefEnumTagLayout>,
+	// `None` if enum does not use a niche, or niche cannot be determined
+	pub niche: Option<DefNiche>,
+}
+
+#[apply(def)]
+pub struct DefEnumTagLayout {
+	pub offset: usize,
+	pub size: usize,
 }
 
 #[apply(def)]
 pub struct DefEnumVariant {
 	pub name: String,
-	pub discriminant: u64,
-	// TODO: Need `offset` here or `discriminant_size` in `DefEnum`
+	// Sign-extended if `repr.int` is signed or absent (`isize`), zero-extended if
+	// unsigned. `#[repr(u128)]` discriminants above `i128::MAX` don't fit, so are
+	// stored with same bits (negative), and must be cast back to `u128`.
+	pub discriminant: i128,
 	pub ser_value: Option<String>,
-	pub value_type_id: Option<TypeId>,
+	pub payload: DefEnumVariantPayload,
+	// Offset of start of payload from start of enum.
+	// `None` if variant has no fields, or offset cannot be determined.
+	pub payload_offset: Option<usize>,
+}
+
+#[apply(def)]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "fields"))]
+pub enum DefEnumVariantPayload {
+	Unit,
+	Tuple(Vec<DefEnumVariantField>),
+	Struct(Vec<DefEnumVariantField>),
+}
+
+impl DefEnumVariantPayload {
+	pub fn fields(&self) -> &[DefEnumVariantField] {
+		match self {
+			DefEnumVariantPayload::Unit => &[],
+			DefEnumVariantPayload::Tuple(fields) | DefEnumVariantPayload::Struct(fields) => fields,
+		}
+	}
+
+	pub fn fields_mut(&mut self) -> &mut [DefEnumVariantField] {
+		match self {
+			DefEnumVariantPayload::Unit => &mut [],
+			DefEnumVariantPayload::Tuple(fields) | DefEnumVariantPayload::Struct(fields) => fields,
+		}
+	}
+}
+
+#[apply(def)]
+pub struct DefEnumVariantField {
+	pub name: String,
+	pub ser_name: String,
+	pub type_id: TypeId,
+	// Offset from start of enum.
+	// Available for `#[repr(C)]` / `#[repr(u8)]` etc enums, where layout is defined.
+	// Otherwise found with `offset_of!` with `nightly_offset_of_enum` feature, or by
+	// constructing the variant from `Default` values of its fields without it. `None` if any
+	// field type of the variant does not implement `Default`, or is a type param.
+	pub offset: Option<usize>,
+	pub flatten: bool,
+	pub skip: bool,
 }
 
 #[apply(def)]
@@ -209,37 +401,259 @@ macro_rules! single_type_param {
 	};
 }
 
-single_type_param!(DefBox);
-single_type_param!(DefVec);
-single_type_param!(DefOption);
 single_type_param!(DefPhantomData);
-single_type_param!(DefCell);
-single_type_param!(DefRefCell);
-single_type_param!(DefMutex);
-single_type_param!(DefRwLock);
-single_type_param!(DefRc);
-single_type_param!(DefArc);
-
-macro_rules! double_type_param {
-	($def:ident, $field1:ident, $field2:ident) => {
+// `#[repr(transparent)]` wrappers (`Pin`, `ManuallyDrop`, `Wrapping`,
+// `Saturating`, `Reverse`), which have same layout and niches as their value
+single_type_param!(DefTransparent);
+// Same size and alignment as value, but may be uninitialized, so has no niches
+single_type_param!(DefMaybeUninit);
+
+/// Pointers to unsized types (`Box<[T]>`, `Box<str>`, `Box<dyn Trait>`) are fat
+/// pointers, with metadata (length or vtable pointer) at `metadata_offset`.
+#[apply(def)]
+pub struct DefBox {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub ptr_offset: usize,
+	// `None` if not a fat pointer
+	pub metadata_offset: Option<usize>,
+}
+
+macro_rules! wrapper {
+	($def:ident) => {
+		#[apply(def)]
+		pub struct $def {
+			pub name: String,
+			pub size: usize,
+			pub align: usize,
+			pub value_type_id: TypeId,
+			pub value_offset: usize,
+		}
+	};
+}
+
+wrapper!(DefCell);
+wrapper!(DefUnsafeCell);
+wrapper!(DefMutex);
+wrapper!(DefRwLock);
+wrapper!(DefOnceLock);
+// Value type is the non-atomic equivalent e.g. `u32` for `AtomicU32`
+wrapper!(DefAtomic);
+
+#[apply(def)]
+pub struct DefRefCell {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub value_offset: usize,
+	// Borrow counter is a signed integer: positive = number of shared borrows,
+	// negative = mutably borrowed
+	pub borrow_offset: usize,
+	pub borrow_size: usize,
+}
+
+// Same layout as `Option` of the value
+#[apply(def)]
+pub struct DefOnceCell {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub value_offset: usize,
+	// `None` if uninitialized state is stored in a tag, or niche cannot be determined
+	pub niche: Option<DefNiche>,
+}
+
+// Collections' internal layout is not described
+#[apply(def)]
+pub struct DefMap {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub key_type_id: TypeId,
+	pub value_type_id: TypeId,
+}
+
+macro_rules! collection {
+	($def:ident) => {
+		#[apply(def)]
+		pub struct $def {
+			pub name: String,
+			pub size: usize,
+			pub align: usize,
+			pub value_type_id: TypeId,
+		}
+	};
+}
+
+collection!(DefSet);
+collection!(DefSeq);
+
+#[apply(def)]
+pub struct DefInlineVec {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub inline_capacity: usize,
+	// `true` if elements move to the heap when inline capacity is exceeded
+	pub spills_to_heap: bool,
+}
+
+#[apply(def)]
+pub struct DefInlineString {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	// In bytes
+	pub inline_capacity: usize,
+	pub spills_to_heap: bool,
+}
+
+// `Rc`, `Arc` and their `Weak`s point to an allocation containing strong and
+// weak counts (`usize`s) and the value. `Weak::new()` has no allocation, and
+// its pointer is `usize::MAX`.
+macro_rules! shared_pointer {
+	($def:ident) => {
 		#[apply(def)]
 		pub struct $def {
 			pub name: String,
 			pub size: usize,
 			pub align: usize,
-			pub $field1: TypeId,
-			pub $field2: TypeId,
+			pub value_type_id: TypeId,
+			// Offsets from start of the allocation
+			pub strong_offset: usize,
+			// Weak count includes 1 held by all strong pointers together
+			pub weak_offset: usize,
+			// `None` if alignment of the value is unknown
+			pub value_offset: Option<usize>,
+			// As in `DefBox`
+			pub ptr_offset: usize,
+			pub metadata_offset: Option<usize>,
 		}
 	};
 }
 
-double_type_param!(DefResult, ok_type_id, err_type_id);
+shared_pointer!(DefRc);
+shared_pointer!(DefArc);
+shared_pointer!(DefRcWeak);
+shared_pointer!(DefArcWeak);
+
+#[apply(def)]
+pub struct DefOption {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	// `None` if `None` is stored in a tag, or niche is unknown.
+	// Types with opaque layout have unknown niche, even if they have one
+	// e.g. `Option<HashMap<K, V>>` is same size as `HashMap<K, V>`.
+	pub niche: Option<DefNiche>,
+}
+
+#[apply(def)]
+pub struct DefResult {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub ok_type_id: TypeId,
+	pub err_type_id: TypeId,
+	// `None` if variant is stored in a tag, or niche cannot be determined
+	pub niche: Option<DefNiche>,
+}
+
+#[apply(def)]
+pub struct DefCow {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	// Type ID of `&B`
+	pub borrowed_type_id: TypeId,
+	pub owned_type_id: TypeId,
+	// `None` if variant is stored in a tag, or niche cannot be determined
+	pub niche: Option<DefNiche>,
+}
+
+#[apply(def)]
+pub struct DefDuration {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	// Seconds (`u64`) and nanoseconds (`u32`). `None` if cannot be determined.
+	pub secs_offset: Option<usize>,
+	pub nanos_offset: Option<usize>,
+}
+
+// Platform-specific layout e.g. `PathBuf`, `Instant`
+#[apply(def)]
+pub struct DefOpaque {
+	pub name: String,
+	// `None` for unsized types (`Path`, `OsStr`, `CStr`)
+	pub size: Option<usize>,
+	pub align: Option<usize>,
+	pub logical_type: DefLogicalType,
+}
+
+#[apply(def)]
+pub enum DefLogicalType {
+	Path,
+	OsStr,
+	CStr,
+	Instant,
+	SystemTime,
+	IpAddr,
+	Ipv4Addr,
+	Ipv6Addr,
+	SocketAddr,
+	SocketAddrV4,
+	SocketAddrV6,
+}
+
+/// Niche used to encode which variant an enum / `Option` / `Result` is,
+/// in place of a tag.
+///
+/// Niche is `size` bytes at `offset` from start of type, read as an unsigned
+/// integer (native endian). If it contains one of `values`, the variant is the
+/// one named in that `DefNicheValue`. Otherwise, it's `untagged_variant`,
+/// and the niche bytes are part of that variant's fields.
+#[apply(def)]
+pub struct DefNiche {
+	pub offset: usize,
+	pub size: usize,
+	pub untagged_variant: String,
+	pub values: Vec<DefNicheValue>,
+}
+
+#[apply(def)]
+pub struct DefNicheValue {
+	pub variant: String,
+	pub value: u128,
+}
+
+#[apply(def)]
+pub struct DefVec {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	// Offsets of pointer, capacity and length, each a `usize`
+	pub ptr_offset: usize,
+	pub cap_offset: usize,
+	pub len_offset: usize,
+}
 
 #[apply(def)]
 pub struct DefString {
 	pub name: String,
 	pub size: usize,
 	pub align: usize,
+	// Offsets of pointer, capacity and length, each a `usize`
+	pub ptr_offset: usize,
+	pub cap_offset: usize,
+	pub len_offset: usize,
 }
 
 #[apply(def)]
@@ -254,4 +668,136 @@ pub struct DefStrSlice {
 	pub name: String,
 	pub size: usize,
 	pub align: usize,
+	pub ptr_offset: usize,
+	pub len_offset: usize,
+}
+
+#[apply(def)]
+pub struct DefTuple {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub elements: Vec<DefTupleElement>,
+}
+
+#[apply(def)]
+pub struct DefTupleElement {
+	pub type_id: TypeId,
+	pub offset: usize,
+}
+
+#[apply(def)]
+pub struct DefArray {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub len: usize,
+}
+
+#[apply(def)]
+pub struct DefSlice {
+	pub name: String,
+	pub size: Option<usize>,
+	pub align: usize,
+	pub value_type_id: TypeId,
+}
+
+#[apply(def)]
+pub struct DefSliceRef {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub mutable: bool,
+	pub ptr_offset: usize,
+	pub len_offset: usize,
+}
+
+#[apply(def)]
+pub struct DefRef {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub mutable: bool,
+	pub fat: bool,
+	// As in `DefBox`
+	pub ptr_offset: usize,
+	pub metadata_offset: Option<usize>,
+}
+
+#[apply(def)]
+pub struct DefPtr {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub value_type_id: TypeId,
+	pub mutable: bool,
+	pub fat: bool,
+	pub non_null: bool,
+	// As in `DefBox`
+	pub ptr_offset: usize,
+	pub metadata_offset: Option<usize>,
+}
+
+/// Function pointer e.g. `fn(u8) -> u16`, `unsafe extern "C" fn()`.
+/// Function pointers are never null, so `Option<fn()>` uses null as a niche.
+/// Higher-ranked function pointers (e.g. `fn(&u8)`, which is `for<'a> fn(&'a
+/// u8)`) are not supported. Use a named lifetime instead e.g. `fn(&'a u8)`.
+#[apply(def)]
+pub struct DefFnPtr {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	pub arg_type_ids: Vec<TypeId>,
+	// `()` if function does not return a value
+	pub return_type_id: TypeId,
+	// e.g. `"Rust"`, `"C"`
+	pub abi: String,
+	pub is_unsafe: bool,
+}
+
+#[apply(def)]
+pub struct DefUnion {
+	pub name: String,
+	pub size: usize,
+	pub align: usize,
+	// All members are at offset 0
+	pub members: Vec<DefUnionMember>,
+	pub repr: DefRepr,
+}
+
+#[apply(def)]
+pub struct DefUnionMember {
+	pub name: String,
+	pub type_id: TypeId,
+}
+
+/// Trait object (`dyn Trait`). Size and alignment depend on the concrete type,
+/// so are always `None`.
+/// Pointers to it (`Box<dyn Trait>`, `&dyn Trait` etc) are fat pointers,
+/// consisting of a data pointer and a vtable pointer at `ptr_offset` and
+/// `vtable_offset`. For `Rc` / `Arc`, data pointer points to the allocation.
+#[apply(def)]
+pub struct DefDyn {
+	pub name: String,
+	pub size: Option<usize>,
+	pub align: Option<usize>,
+	pub ptr_offset: usize,
+	pub vtable_offset: usize,
+	// `None` if set of implementors is not declared
+	pub implementor_type_ids: Option<Vec<TypeId>>,
+}
+
+/// `#[repr(...)]` attributes.
+/// `int` is integer repr of an enum e.g. `"u8"` for `#[repr(u8)]`.
+#[apply(def)]
+#[derive(Default)]
+pub struct DefRepr {
+	pub c: bool,
+	pub transparent: bool,
+	pub packed: Option<usize>,
+	pub align: Option<usize>,
+	pub int: Option<String>,
 }
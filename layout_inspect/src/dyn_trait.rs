@@ -0,0 +1,107 @@
+use std::{
+	fmt::Debug,
+	mem::{size_of, transmute_copy},
+};
+
+/// Implement `Inspect` for a trait object e.g. `inspect_dyn!(dyn Plugin)`,
+/// and references to it. `Box<dyn Plugin>`, `Rc<dyn Plugin>`, `Arc<dyn Plugin>`
+/// and raw pointers are then covered by their generic impls.
+///
+/// Types implementing the trait can be listed after `=>`
+/// e.g. `inspect_dyn!(dyn Plugin => Foo, Bar)`, to include their IDs in
+/// `DefDyn`.
+#[macro_export]
+macro_rules! inspect_dyn {
+	(@ref $prefix:literal, $mutable:literal, $type:ty, $($ref:tt)+) => {
+		impl<'a> $crate::Inspect for $($ref)+ $type {
+			fn name() -> ::std::string::String {
+				$prefix.to_string() + &<$type as $crate::Inspect>::name()
+			}
+
+			fn size() -> ::std::option::Option<usize> {
+				Some(::std::mem::size_of::<Self>())
+			}
+
+			fn align() -> ::std::option::Option<usize> {
+				Some(::std::mem::align_of::<Self>())
+			}
+
+			fn def(collector: &mut $crate::TypesCollector) -> $crate::defs::DefType {
+				let value_type_id = collector.collect::<$type>();
+				let (ptr_offset, metadata_offset) = $crate::__private::pointer_offsets(
+					::std::mem::size_of::<Self>(),
+					value_type_id,
+					collector,
+				);
+				$crate::defs::DefType::Ref($crate::defs::DefRef {
+					name: <Self as $crate::Inspect>::name(),
+					size: <Self as $crate::Inspect>::size().unwrap(),
+					align: <Self as $crate::Inspect>::align().unwrap(),
+					value_type_id,
+					mutable: $mutable,
+					fat: true,
+					ptr_offset,
+					metadata_offset,
+				})
+			}
+		}
+	};
+	($type:ty $(=> $($implementor:ty),+ $(,)?)?) => {
+		impl $crate::Inspect for $type {
+			fn name() -> ::std::string::String {
+				::std::stringify!($type).to_string()
+			}
+
+			fn size() -> ::std::option::Option<usize> {
+				None
+			}
+
+			fn align() -> ::std::option::Option<usize> {
+				None
+			}
+
+			fn def(collector: &mut $crate::TypesCollector) -> $crate::defs::DefType {
+				let (ptr_offset, vtable_offset) = $crate::__private::dyn_ptr_offsets();
+				let implementor_type_ids = $crate::inspect_dyn!(
+					@implementors collector $(, $($implementor),+)?
+				);
+				$crate::defs::DefType::Dyn($crate::defs::DefDyn {
+					name: <Self as $crate::Inspect>::name(),
+					size: None,
+					align: None,
+					ptr_offset,
+					vtable_offset,
+					implementor_type_ids,
+				})
+			}
+		}
+
+		$crate::inspect_dyn!(@ref "&", false, $type, &'a);
+		$crate::inspect_dyn!(@ref "&mut ", true, $type, &'a mut);
+	};
+	(@implementors $collector:ident) => {
+		None
+	};
+	(@implementors $collector:ident, $($implementor:ty),+) => {
+		Some(vec![$($collector.collect::<$implementor>()),+])
+	};
+}
+
+/// Get offsets of data pointer and vtable pointer within a trait object
+/// fat pointer. Order is not guaranteed, so determine it by creating one
+/// and finding which word contains the data pointer.
+/// All trait objects have the same layout, so use `dyn Debug`.
+#[doc(hidden)]
+pub fn dyn_ptr_offsets() -> (usize, usize) {
+	let value = 0u8;
+	let value_ptr = &value as *const u8;
+	let obj: &dyn Debug = &value;
+	// SAFETY: Trait object references are always 2 `usize`s
+	assert_eq!(size_of::<&dyn Debug>(), size_of::<[usize; 2]>());
+	let words: [usize; 2] = unsafe { transmute_copy(&obj) };
+	if words[0] == value_ptr as usize {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	}
+}
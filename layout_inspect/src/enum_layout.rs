@@ -0,0 +1,168 @@
+use std::marker::PhantomData;
+
+use crate::{
+	defs::{DefEnum, DefEnumTagLayout},
+	niche::enum_niche,
+	TypesCollector,
+};
+
+/// Size and alignment of a field
+pub type FieldLayout = (usize, usize);
+
+/// Complete layout info for an enum.
+///
+/// Sets `tag_layout`, `niche` and each variant's `payload_offset`.
+/// Where enum's layout is defined by `#[repr]`, also sets offsets of variant
+/// fields.
+///
+/// `tag_size` is size of a fieldless enum with same variants, discriminants and
+/// `#[repr]` as this one. `field_layouts` contains size and alignment of each
+/// variant's fields, in order of declaration.
+///
+/// Used by `Inspect` derive macro.
+pub fn complete_enum_layout(
+	def: &mut DefEnum,
+	tag_size: usize,
+	field_layouts: &[Vec<FieldLayout>],
+	collector: &TypesCollector,
+) {
+	if def.repr.c || def.repr.int.is_some() {
+		set_defined_layout(def, tag_size, field_layouts);
+	} else {
+		set_rust_layout(def, tag_size, field_layouts);
+		def.niche = enum_niche(def, collector);
+	}
+}
+
+/// Set layout for `#[repr(C)]`, `#[repr(u8)]`, `#[repr(C, u8)]` etc enums.
+/// Layout is defined by RFC 2195:
+/// https://rust-lang.github.io/rfcs/2195-really-tagged-unions.html
+fn set_defined_layout(def: &mut DefEnum, tag_size: usize, field_layouts: &[Vec<FieldLayout>]) {
+	def.tag_layout = Some(DefEnumTagLayout {
+		offset: 0,
+		size: tag_size,
+	});
+
+	// `#[repr(C)]`: Tag followed by a union of `#[repr(C)]` structs, one for each
+	// variant's fields. `#[repr(u8)]`: Union of `#[repr(C)]` structs, each with the
+	// tag as first field.
+	let payload_start = if def.repr.c {
+		let union_align = field_layouts
+			.iter()
+			.flatten()
+			.map(|&(_, align)| align)
+			.max()
+			.unwrap_or(1);
+		round_up(tag_size, union_align)
+	} else {
+		tag_size
+	};
+
+	for (variant, layouts) in def.variants.iter_mut().zip(field_layouts) {
+		let mut end = payload_start;
+		for (field, &(size, align)) in variant.payload.fields_mut().iter_mut().zip(layouts) {
+			let offset = round_up(end, align);
+			field.offset = Some(offset);
+			end = offset + size;
+		}
+		variant.payload_offset = variant
+			.payload
+			.fields()
+			.first()
+			.and_then(|field| field.offset);
+	}
+}
+
+/// Set layout for enums with default repr.
+/// Layout is not defined, so is deduced from field offsets, where they're
+/// available.
+fn set_rust_layout(def: &mut DefEnum, tag_size: usize, field_layouts: &[Vec<FieldLayout>]) {
+	// Payload starts at lowest field offset
+	for variant in &mut def.variants {
+		let offsets: Option<Vec<usize>> = variant
+			.payload
+			.fields()
+			.iter()
+			.map(|field| field.offset)
+			.collect();
+		variant.payload_offset = offsets.and_then(|offsets| offsets.into_iter().min());
+	}
+
+	// Single-variant enums have no tag
+	if def.variants.len() < 2 {
+		return;
+	}
+
+	// Fieldless enums consist only of tag, plus any padding from `#[repr(align)]`
+	if field_layouts.iter().all(|layouts| layouts.is_empty()) {
+		def.tag_layout = Some(DefEnumTagLayout {
+			offset: 0,
+			size: tag_size,
+		});
+		return;
+	}
+
+	// Tag is always at start of enum. If any non-zero-sized field overlaps it,
+	// enum has no tag and instead stores discriminant in a niche.
+	// Tag is widened to fill space before first field, so its size is offset of
+	// lowest field (rounded down to an integer size).
+	let mut min_offset = def.size;
+	for (variant, layouts) in def.variants.iter().zip(field_layouts) {
+		for (field, &(size, _)) in variant.payload.fields().iter().zip(layouts) {
+			if size == 0 {
+				continue;
+			}
+			match field.offset {
+				Some(offset) => min_offset = min_offset.min(offset),
+				None => return,
+			}
+		}
+	}
+
+	if min_offset >= tag_size {
+		def.tag_layout = Some(DefEnumTagLayout {
+			offset: 0,
+			size: 1 << min_offset.ilog2(),
+		});
+	}
+}
+
+fn round_up(offset: usize, align: usize) -> usize {
+	(offset + align - 1) / align * align
+}
+
+/// Construct a value with `Default`, where type implements it.
+///
+/// Used by `Inspect` derive macro to construct enum variants, to find offsets
+/// of their fields without `offset_of!` on enum variants, which is unstable.
+/// `(&DefaultProbe::<T>::new()).probe()` resolves to `ProbeDefault::probe` if
+/// `T: Default`, otherwise to `ProbeNone::probe` (autoref specialization).
+/// Always resolves to `ProbeNone::probe` where `T` is a type param.
+pub struct DefaultProbe<T>(PhantomData<T>);
+
+impl<T> DefaultProbe<T> {
+	#[allow(clippy::new_without_default)]
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+pub trait ProbeDefault<T> {
+	fn probe(&self) -> Option<T>;
+}
+
+impl<T: Default> ProbeDefault<T> for DefaultProbe<T> {
+	fn probe(&self) -> Option<T> {
+		Some(T::default())
+	}
+}
+
+pub trait ProbeNone<T> {
+	fn probe(&self) -> Option<T>;
+}
+
+impl<T> ProbeNone<T> for &DefaultProbe<T> {
+	fn probe(&self) -> Option<T> {
+		None
+	}
+}
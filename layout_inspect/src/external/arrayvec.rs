@@ -0,0 +1,57 @@
+use std::mem::{align_of, size_of};
+
+use arrayvec::{ArrayString, ArrayVec};
+
+use crate::{
+	defs::{DefInlineString, DefInlineVec, DefType},
+	Inspect, TypesCollector,
+};
+
+impl<T: Inspect, const CAP: usize> Inspect for ArrayVec<T, CAP> {
+	fn name() -> String {
+		format!("ArrayVec<{},{}>", T::name(), CAP)
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::InlineVec(DefInlineVec {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<T>(),
+			inline_capacity: CAP,
+			spills_to_heap: false,
+		})
+	}
+}
+
+impl<const CAP: usize> Inspect for ArrayString<CAP> {
+	fn name() -> String {
+		format!("ArrayString<{}>", CAP)
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(_collector: &mut TypesCollector) -> DefType {
+		DefType::InlineString(DefInlineString {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			inline_capacity: CAP,
+			spills_to_heap: false,
+		})
+	}
+}
@@ -0,0 +1,39 @@
+use std::mem::{align_of, size_of};
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{
+	defs::{DefSeq, DefType},
+	Inspect, TypesCollector,
+};
+
+// `Bytes` and `BytesMut` are sequences of `u8` with opaque internal layout
+macro_rules! bytes {
+	($name:ident) => {
+		impl Inspect for $name {
+			fn name() -> String {
+				stringify!($name).to_string()
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				DefType::Seq(DefSeq {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id: collector.collect::<u8>(),
+				})
+			}
+		}
+	};
+}
+
+bytes!(Bytes);
+bytes!(BytesMut);
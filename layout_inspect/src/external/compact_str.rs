@@ -0,0 +1,36 @@
+use std::mem::{align_of, size_of};
+
+use compact_str::CompactString;
+
+use super::inline_string_capacity;
+use crate::{
+	defs::{DefInlineString, DefType},
+	Inspect, TypesCollector,
+};
+
+impl Inspect for CompactString {
+	fn name() -> String {
+		"CompactString".to_string()
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(_collector: &mut TypesCollector) -> DefType {
+		DefType::InlineString(DefInlineString {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			inline_capacity: inline_string_capacity(
+				|text| CompactString::new(text),
+				CompactString::is_heap_allocated,
+			),
+			spills_to_heap: true,
+		})
+	}
+}
@@ -0,0 +1,61 @@
+use std::mem::{align_of, size_of};
+
+use hashbrown::{DefaultHashBuilder, HashMap, HashSet};
+
+use crate::{
+	defs::{DefMap, DefSet, DefType},
+	impls::hasher_name,
+	Inspect, TypesCollector,
+};
+
+// Hasher is only included in name if not the default
+impl<K: Inspect, V: Inspect, S> Inspect for HashMap<K, V, S> {
+	fn name() -> String {
+		"hashbrown::HashMap<".to_string()
+			+ &K::name()
+			+ "," + &V::name()
+			+ &hasher_name::<S, DefaultHashBuilder>()
+			+ ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Map(DefMap {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			key_type_id: collector.collect::<K>(),
+			value_type_id: collector.collect::<V>(),
+		})
+	}
+}
+
+impl<T: Inspect, S> Inspect for HashSet<T, S> {
+	fn name() -> String {
+		"hashbrown::HashSet<".to_string() + &T::name() + &hasher_name::<S, DefaultHashBuilder>() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Set(DefSet {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<T>(),
+		})
+	}
+}
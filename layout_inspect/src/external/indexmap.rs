@@ -0,0 +1,60 @@
+use std::{
+	collections::hash_map::RandomState,
+	mem::{align_of, size_of},
+};
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+	defs::{DefMap, DefSet, DefType},
+	impls::hasher_name,
+	Inspect, TypesCollector,
+};
+
+// Hasher is only included in name if not the default
+impl<K: Inspect, V: Inspect, S> Inspect for IndexMap<K, V, S> {
+	fn name() -> String {
+		"IndexMap<".to_string() + &K::name() + "," + &V::name() + &hasher_name::<S, RandomState>() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Map(DefMap {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			key_type_id: collector.collect::<K>(),
+			value_type_id: collector.collect::<V>(),
+		})
+	}
+}
+
+impl<T: Inspect, S> Inspect for IndexSet<T, S> {
+	fn name() -> String {
+		"IndexSet<".to_string() + &T::name() + &hasher_name::<S, RandomState>() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Set(DefSet {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<T>(),
+		})
+	}
+}
@@ -0,0 +1,32 @@
+// Impls for types from other crates, each behind a feature of the same name
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "compact_str")]
+mod compact_str;
+#[cfg(feature = "hashbrown")]
+mod hashbrown;
+#[cfg(feature = "indexmap")]
+mod indexmap;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "smol_str")]
+mod smol_str;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+/// Find how many bytes a small-string type can store without allocating,
+/// by creating strings of increasing length until one is heap allocated.
+#[cfg(any(feature = "compact_str", feature = "smol_str"))]
+fn inline_string_capacity<S>(
+	new: impl Fn(&str) -> S,
+	is_heap_allocated: impl Fn(&S) -> bool,
+) -> usize {
+	let max = "a".repeat(std::mem::size_of::<S>());
+	(0..=max.len())
+		.take_while(|&len| !is_heap_allocated(&new(&max[..len])))
+		.last()
+		.unwrap_or(0)
+}
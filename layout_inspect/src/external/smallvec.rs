@@ -0,0 +1,37 @@
+use std::mem::{align_of, size_of};
+
+use smallvec::{Array, SmallVec};
+
+use crate::{
+	defs::{DefInlineVec, DefType},
+	Inspect, TypesCollector,
+};
+
+impl<A> Inspect for SmallVec<A>
+where
+	A: Array + Inspect,
+	A::Item: Inspect,
+{
+	fn name() -> String {
+		"SmallVec<".to_string() + &A::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::InlineVec(DefInlineVec {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<A::Item>(),
+			inline_capacity: <A as Array>::size(),
+			spills_to_heap: true,
+		})
+	}
+}
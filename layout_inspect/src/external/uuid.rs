@@ -0,0 +1,32 @@
+use std::mem::{align_of, size_of};
+
+use uuid::Uuid;
+
+use crate::{
+	defs::{DefTransparent, DefType},
+	Inspect, TypesCollector,
+};
+
+// `Uuid` is `#[repr(transparent)]` wrapper around `[u8; 16]`
+impl Inspect for Uuid {
+	fn name() -> String {
+		"Uuid".to_string()
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Transparent(DefTransparent {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<[u8; 16]>(),
+		})
+	}
+}
@@ -1,17 +1,48 @@
 use std::{
-	cell::{Cell, RefCell},
+	any,
+	borrow::Cow,
+	cell::{Cell, OnceCell, RefCell, UnsafeCell},
+	cmp::{self, Reverse},
+	collections::{
+		hash_map::RandomState, BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque,
+	},
+	ffi::{CStr, CString, OsStr, OsString},
 	marker::PhantomData,
-	mem::{align_of, size_of},
-	rc::Rc,
-	sync::{Arc, Mutex, RwLock},
+	mem::{align_of, align_of_val, size_of, transmute_copy, ManuallyDrop, MaybeUninit},
+	net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+	num::{Saturating, Wrapping},
+	ops::{Range, RangeInclusive},
+	panic,
+	path::{Path, PathBuf},
+	pin::Pin,
+	ptr::NonNull,
+	rc::{self, Rc},
+	slice,
+	sync::{
+		self,
+		atomic::{
+			AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicPtr, AtomicU16,
+			AtomicU32, AtomicU64, AtomicU8, AtomicUsize,
+		},
+		Arc, Mutex, OnceLock, RwLock,
+	},
+	thread,
+	time::{Duration, Instant, SystemTime},
 };
 
+use memoffset::{offset_of, offset_of_tuple};
+
 use crate::{
 	defs::{
-		DefArc, DefBox, DefCell, DefMutex, DefOption, DefPhantomData, DefRc, DefRefCell, DefResult,
-		DefRwLock, DefStr, DefStrSlice, DefString, DefType, DefVec,
+		DefArc, DefArcWeak, DefArray, DefAtomic, DefBox, DefCell, DefCow, DefDuration, DefEnum,
+		DefEnumTag, DefEnumTagLayout, DefEnumVariant, DefEnumVariantPayload, DefFnPtr, DefLogicalType,
+		DefMap, DefMaybeUninit, DefMutex, DefOnceCell, DefOnceLock, DefOpaque, DefOption,
+		DefPhantomData, DefPtr, DefRc, DefRcWeak, DefRef, DefRefCell, DefRepr, DefResult, DefRwLock,
+		DefSeq, DefSet, DefSlice, DefSliceRef, DefStr, DefStrSlice, DefString, DefStruct,
+		DefStructField, DefTransparent, DefTuple, DefTupleElement, DefType, DefUnsafeCell, DefVec,
 	},
-	Inspect, TypesCollector,
+	niche::{cow_niche, option_niche, result_niche},
+	Inspect, TypeId, TypesCollector,
 };
 
 impl Inspect for String {
@@ -28,10 +59,14 @@ impl Inspect for String {
 	}
 
 	fn def(_collector: &mut TypesCollector) -> DefType {
+		let (ptr_offset, cap_offset, len_offset) = string_offsets();
 		DefType::String(DefString {
 			name: Self::name(),
 			size: Self::size().unwrap(),
 			align: Self::align().unwrap(),
+			ptr_offset,
+			cap_offset,
+			len_offset,
 		})
 	}
 }
@@ -72,14 +107,190 @@ impl<'a> Inspect for &'a str {
 	}
 
 	fn def(_collector: &mut TypesCollector) -> DefType {
+		let (ptr_offset, len_offset) = slice_ref_offsets::<u8>();
 		DefType::StrSlice(DefStrSlice {
 			name: Self::name(),
 			size: Self::size().unwrap(),
 			align: Self::align().unwrap(),
+			ptr_offset,
+			len_offset,
+		})
+	}
+}
+
+impl<T: Inspect, const N: usize> Inspect for [T; N] {
+	fn name() -> String {
+		format!("[{}; {}]", T::name(), N)
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Array(DefArray {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<T>(),
+			len: N,
+		})
+	}
+}
+
+impl<T: Inspect> Inspect for [T] {
+	fn name() -> String {
+		"[".to_string() + &T::name() + "]"
+	}
+
+	fn size() -> Option<usize> {
+		None
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<T>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Slice(DefSlice {
+			name: Self::name(),
+			size: Self::size(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<T>(),
 		})
 	}
 }
 
+macro_rules! slice_ref {
+	($prefix:literal, $mutable:literal, $($ref:tt)+) => {
+		impl<'a, T: Inspect> Inspect for $($ref)+ [T] {
+			fn name() -> String {
+				$prefix.to_string() + &<[T]>::name()
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				let (ptr_offset, len_offset) = slice_ref_offsets::<T>();
+				DefType::SliceRef(DefSliceRef {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id: collector.collect::<[T]>(),
+					mutable: $mutable,
+					ptr_offset,
+					len_offset,
+				})
+			}
+		}
+	};
+}
+
+slice_ref!("&", false, &'a);
+slice_ref!("&mut ", true, &'a mut);
+
+macro_rules! reference {
+	($prefix:literal, $mutable:literal, $($ref:tt)+) => {
+		// `str` and `[T]` have their own impls, so only sized pointees here
+		impl<'a, T: Inspect> Inspect for $($ref)+ T {
+			fn name() -> String {
+				$prefix.to_string() + &T::name()
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				DefType::Ref(DefRef {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id: collector.collect::<T>(),
+					mutable: $mutable,
+					fat: false,
+					ptr_offset: 0,
+					metadata_offset: None,
+				})
+			}
+		}
+	};
+}
+
+reference!("&", false, &'a);
+reference!("&mut ", true, &'a mut);
+
+macro_rules! pointer {
+	($type:ty, $prefix:literal, $suffix:literal, $mutable:literal, $non_null:literal) => {
+		impl<T: Inspect + ?Sized> Inspect for $type {
+			fn name() -> String {
+				$prefix.to_string() + &T::name() + $suffix
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				let value_type_id = collector.collect::<T>();
+				let (ptr_offset, metadata_offset) =
+					pointer_offsets(size_of::<Self>(), value_type_id, collector);
+				DefType::Ptr(DefPtr {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id,
+					mutable: $mutable,
+					// Pointers to unsized types carry metadata
+					fat: metadata_offset.is_some(),
+					non_null: $non_null,
+					ptr_offset,
+					metadata_offset,
+				})
+			}
+		}
+	};
+}
+
+pointer!(*const T, "*const ", "", false, false);
+pointer!(*mut T, "*mut ", "", true, false);
+pointer!(NonNull<T>, "NonNull<", ">", true, true);
+
+/// Get offsets of data pointer and length within a slice fat pointer.
+/// Order of the 2 fields is not guaranteed, so determine it by creating a slice
+/// and finding which word contains the pointer.
+fn slice_ref_offsets<T>() -> (usize, usize) {
+	let ptr = NonNull::<T>::dangling().as_ptr();
+	// SAFETY: Dangling pointer is valid for a zero-length slice
+	let slice: &[T] = unsafe { slice::from_raw_parts(ptr, 0) };
+	// SAFETY: Slice references are always 2 `usize`s
+	let words: [usize; 2] = unsafe { transmute_copy(&slice) };
+	// Dangling pointer is never 0, so cannot be confused with length
+	if words[0] == ptr as usize {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	}
+}
+
 macro_rules! single_type_param {
 	($name:ident, $def:ident) => {
 		impl<T: Inspect> Inspect for $name<T> {
@@ -107,22 +318,63 @@ macro_rules! single_type_param {
 	};
 }
 
-single_type_param!(Box, DefBox);
-single_type_param!(Vec, DefVec);
-single_type_param!(Option, DefOption);
 single_type_param!(PhantomData, DefPhantomData);
-single_type_param!(Cell, DefCell);
-single_type_param!(RefCell, DefRefCell);
-single_type_param!(Mutex, DefMutex);
-single_type_param!(RwLock, DefRwLock);
-single_type_param!(Rc, DefRc);
-single_type_param!(Arc, DefArc);
-
-macro_rules! double_type_param {
-	($name:ident, $def:ident, $field1:ident, $field2:ident) => {
-		impl<T: Inspect, T2: Inspect> Inspect for $name<T, T2> {
+
+impl<T: Inspect + ?Sized> Inspect for Box<T> {
+	fn name() -> String {
+		"Box<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let value_type_id = collector.collect::<T>();
+		let (ptr_offset, metadata_offset) =
+			pointer_offsets(size_of::<Self>(), value_type_id, collector);
+		DefType::Box(DefBox {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id,
+			ptr_offset,
+			metadata_offset,
+		})
+	}
+}
+
+/// Get offsets of data pointer and metadata within a pointer of `size` bytes
+/// to type `value_type_id`. Metadata is length for slices and `str`, or vtable
+/// pointer for trait objects. Metadata offset is `None` for thin pointers.
+/// Also used by `inspect_dyn!` and `Inspect` derive macro.
+#[doc(hidden)]
+pub fn pointer_offsets(
+	size: usize,
+	value_type_id: TypeId,
+	collector: &TypesCollector,
+) -> (usize, Option<usize>) {
+	if size == size_of::<usize>() {
+		return (0, None);
+	}
+	// Trait objects' pointer layout is found by `inspect_dyn!`.
+	// All other unsized types have a length, like slices.
+	let (ptr_offset, metadata_offset) = match collector.get(value_type_id) {
+		Some(DefType::Dyn(def)) => (def.ptr_offset, def.vtable_offset),
+		_ => slice_ref_offsets::<u8>(),
+	};
+	(ptr_offset, Some(metadata_offset))
+}
+
+macro_rules! wrapper {
+	($name:ident, $def:ident, $value_offset:expr) => {
+		impl<T: Inspect> Inspect for $name<T> {
 			fn name() -> String {
-				stringify!($name).to_string() + "<" + &T::name() + "," + &T2::name() + ">"
+				stringify!($name).to_string() + "<" + &T::name() + ">"
 			}
 
 			fn size() -> Option<usize> {
@@ -138,12 +390,1014 @@ macro_rules! double_type_param {
 					name: Self::name(),
 					size: Self::size().unwrap(),
 					align: Self::align().unwrap(),
-					$field1: collector.collect::<T>(),
-					$field2: collector.collect::<T2>(),
+					value_type_id: collector.collect::<T>(),
+					value_offset: $value_offset,
+				})
+			}
+		}
+	};
+}
+
+// `Cell` and `UnsafeCell` are `#[repr(transparent)]`
+wrapper!(Cell, DefCell, 0);
+wrapper!(UnsafeCell, DefUnsafeCell, 0);
+wrapper!(
+	Mutex,
+	DefMutex,
+	probe_with_stack_for::<T, _>(mutex_value_offset::<T>)
+);
+wrapper!(
+	RwLock,
+	DefRwLock,
+	probe_with_stack_for::<T, _>(rw_lock_value_offset::<T>)
+);
+wrapper!(
+	OnceLock,
+	DefOnceLock,
+	probe_with_stack_for::<T, _>(once_lock_value_offset::<T>)
+);
+
+impl<T: Inspect> Inspect for RefCell<T> {
+	fn name() -> String {
+		"RefCell<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let (value_offset, borrow_offset) = probe_with_stack_for::<T, _>(ref_cell_offsets::<T>);
+		DefType::RefCell(DefRefCell {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<T>(),
+			value_offset,
+			borrow_offset,
+			borrow_size: size_of::<isize>(),
+		})
+	}
+}
+
+impl<T: Inspect> Inspect for OnceCell<T> {
+	fn name() -> String {
+		"OnceCell<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let value_type_id = collector.collect::<T>();
+		// `OnceCell<T>` is an `UnsafeCell<Option<T>>`. `Some` payload is at start
+		// if `None` is stored in a niche, or follows the tag otherwise.
+		// Either way, it's at the end.
+		DefType::OnceCell(DefOnceCell {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id,
+			value_offset: size_of::<Self>() - size_of::<T>(),
+			niche: option_niche(value_type_id, Self::size().unwrap(), collector),
+		})
+	}
+}
+
+// Offsets of values in `Mutex`, `RwLock`, `OnceLock` and `RefCell` are found
+// by locking / borrowing a wrapper containing a `MaybeUninit<T>`.
+// Value is wrapped in an `UnsafeCell`, which hides its niches, so layout
+// of the wrapper only depends on size and alignment of the value,
+// which are same for `MaybeUninit<T>`.
+
+/// Run a probe which creates a wrapper around a `MaybeUninit<T>` on the stack.
+/// Unoptimized builds copy the value several times while moving it into the
+/// wrapper, so where `T` is large, run probe on a thread with a stack large
+/// enough for it.
+fn probe_with_stack_for<T, R: Send + 'static>(probe: fn() -> R) -> R {
+	const MAX_INLINE_SIZE: usize = 4 * 1024;
+	// `OnceLock::get_or_init` needs about 7 copies of value
+	const COPIES: usize = 16;
+
+	if size_of::<T>() <= MAX_INLINE_SIZE {
+		return probe();
+	}
+
+	let stack_size = size_of::<T>()
+		.saturating_mul(COPIES)
+		.saturating_add(1 << 20);
+	match thread::Builder::new().stack_size(stack_size).spawn(probe) {
+		Ok(handle) => {
+			handle
+				.join()
+				.unwrap_or_else(|err| panic::resume_unwind(err))
+		}
+		// Threads unsupported on this platform
+		Err(_) => probe(),
+	}
+}
+
+fn mutex_value_offset<T>() -> usize {
+	let mutex = Mutex::new(MaybeUninit::<T>::uninit());
+	let guard = mutex.lock().unwrap();
+	let value_ptr: *const MaybeUninit<T> = &*guard;
+	value_ptr as usize - &mutex as *const _ as usize
+}
+
+fn rw_lock_value_offset<T>() -> usize {
+	let lock = RwLock::new(MaybeUninit::<T>::uninit());
+	let guard = lock.read().unwrap();
+	let value_ptr: *const MaybeUninit<T> = &*guard;
+	value_ptr as usize - &lock as *const _ as usize
+}
+
+fn once_lock_value_offset<T>() -> usize {
+	let lock = OnceLock::new();
+	let value_ptr: *const MaybeUninit<T> = lock.get_or_init(MaybeUninit::uninit);
+	value_ptr as usize - &lock as *const _ as usize
+}
+
+/// Get offsets of value and borrow counter within a `RefCell`.
+/// Borrow counter is found by holding 2 shared borrows, which sets it to 2.
+fn ref_cell_offsets<T>() -> (usize, usize) {
+	let cell = RefCell::new(MaybeUninit::<T>::uninit());
+	let cell_ptr = &cell as *const _ as *const u8;
+	let value_offset = cell.as_ptr() as usize - cell_ptr as usize;
+
+	let _borrows = (cell.borrow(), cell.borrow());
+	let borrow_offset = (0..size_of::<RefCell<MaybeUninit<T>>>())
+		.step_by(size_of::<isize>())
+		.filter(|&offset| {
+			// Value is uninitialized, so must not be read
+			offset + size_of::<isize>() <= value_offset || offset >= value_offset + size_of::<T>()
+		})
+		.find(|&offset| {
+			// SAFETY: Offset is within the `RefCell` and outside the value,
+			// so is the initialized borrow counter. Borrows do not modify it.
+			let value = unsafe { (cell_ptr.add(offset) as *const isize).read() };
+			value == 2
+		})
+		.unwrap();
+	(value_offset, borrow_offset)
+}
+
+macro_rules! atomic {
+	($name:ident, $value:ty) => {
+		impl Inspect for $name {
+			fn name() -> String {
+				stringify!($name).to_string()
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				// Atomics have same in-memory representation as their non-atomic type
+				DefType::Atomic(DefAtomic {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id: collector.collect::<$value>(),
+					value_offset: 0,
+				})
+			}
+		}
+	};
+}
+
+atomic!(AtomicBool, bool);
+atomic!(AtomicU8, u8);
+atomic!(AtomicU16, u16);
+atomic!(AtomicU32, u32);
+atomic!(AtomicU64, u64);
+atomic!(AtomicUsize, usize);
+atomic!(AtomicI8, i8);
+atomic!(AtomicI16, i16);
+atomic!(AtomicI32, i32);
+atomic!(AtomicI64, i64);
+atomic!(AtomicIsize, isize);
+
+impl<T: Inspect> Inspect for AtomicPtr<T> {
+	fn name() -> String {
+		"AtomicPtr<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		DefType::Atomic(DefAtomic {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<*mut T>(),
+			value_offset: 0,
+		})
+	}
+}
+
+impl<T: Inspect> Inspect for Vec<T> {
+	fn name() -> String {
+		"Vec<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let (ptr_offset, cap_offset, len_offset) = vec_offsets();
+		DefType::Vec(DefVec {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id: collector.collect::<T>(),
+			ptr_offset,
+			cap_offset,
+			len_offset,
+		})
+	}
+}
+
+/// Get offsets of pointer, capacity and length in `Vec<T>`.
+/// Order of fields is not guaranteed, so find them by inspecting bytes of
+/// an empty `Vec` with non-zero capacity.
+/// All `Vec<T>`s have same fields, so `Vec<u8>` is used for any `T`. This
+/// avoids allocating for large `T`, and zero-sized `T`s, whose capacity is
+/// stored as 0 (same as length).
+fn vec_offsets() -> (usize, usize, usize) {
+	let vec = Vec::<u8>::with_capacity(1);
+	word_offsets(&vec, vec.as_ptr() as usize, vec.capacity(), vec.len())
+}
+
+/// Get offsets of pointer, capacity and length in `String`.
+fn string_offsets() -> (usize, usize, usize) {
+	let string = String::with_capacity(1);
+	word_offsets(
+		&string,
+		string.as_ptr() as usize,
+		string.capacity(),
+		string.len(),
+	)
+}
+
+/// Find offsets of 3 distinct `usize`s in a value made up of 3 `usize`s.
+/// Heap pointer cannot be 0 or equal to a small capacity, and capacity is
+/// non-zero, so pointer, capacity and length (0) are always distinct.
+fn word_offsets<V>(value: &V, ptr: usize, cap: usize, len: usize) -> (usize, usize, usize) {
+	assert_eq!(size_of::<V>(), size_of::<[usize; 3]>());
+	// SAFETY: Checked size above. `Vec`s and `String`s are 3 initialized `usize`s.
+	let words: [usize; 3] = unsafe { transmute_copy(value) };
+	let offset_of = |target: usize| {
+		let index = words.iter().position(|&word| word == target).unwrap();
+		index * size_of::<usize>()
+	};
+	(offset_of(ptr), offset_of(cap), offset_of(len))
+}
+
+/// Suffix for name of a hashed collection, naming hasher `S` unless it is the
+/// collection's default hasher `D`. Different hashers must give different
+/// names, as names are used as type IDs with `unique_names` feature.
+pub(crate) fn hasher_name<S, D>() -> String {
+	let hasher = any::type_name::<S>();
+	if hasher == any::type_name::<D>() {
+		String::new()
+	} else {
+		",".to_string() + hasher
+	}
+}
+
+macro_rules! map {
+	(@impl $name:ident, <K, V $(, $hasher:ident)?>, $hasher_name:expr) => {
+		impl<K: Inspect, V: Inspect $(, $hasher)?> Inspect for $name<K, V $(, $hasher)?> {
+			fn name() -> String {
+				stringify!($name).to_string() + "<" + &K::name() + "," + &V::name() + $hasher_name + ">"
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				DefType::Map(DefMap {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					key_type_id: collector.collect::<K>(),
+					value_type_id: collector.collect::<V>(),
+				})
+			}
+		}
+	};
+	($name:ident) => {
+		map!(@impl $name, <K, V>, "");
+	};
+	($name:ident, $default_hasher:ty) => {
+		map!(@impl $name, <K, V, S>, &hasher_name::<S, $default_hasher>());
+	};
+}
+
+map!(HashMap, RandomState);
+map!(BTreeMap);
+
+macro_rules! collection {
+	(@impl $name:ident, <T $(, $hasher:ident)?>, $variant:ident, $def:ident, $hasher_name:expr) => {
+		impl<T: Inspect $(, $hasher)?> Inspect for $name<T $(, $hasher)?> {
+			fn name() -> String {
+				stringify!($name).to_string() + "<" + &T::name() + $hasher_name + ">"
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				DefType::$variant($def {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id: collector.collect::<T>(),
+				})
+			}
+		}
+	};
+	($name:ident, $variant:ident, $def:ident) => {
+		collection!(@impl $name, <T>, $variant, $def, "");
+	};
+	($name:ident, $default_hasher:ty, $variant:ident, $def:ident) => {
+		collection!(@impl $name, <T, S>, $variant, $def, &hasher_name::<S, $default_hasher>());
+	};
+}
+
+collection!(HashSet, RandomState, Set, DefSet);
+collection!(BTreeSet, Set, DefSet);
+collection!(VecDeque, Seq, DefSeq);
+collection!(BinaryHeap, Seq, DefSeq);
+collection!(LinkedList, Seq, DefSeq);
+
+macro_rules! shared_pointer {
+	($type:ty, $name:literal, $variant:ident, $def:ident, $layout:ident) => {
+		impl<T: Inspect + ?Sized> Inspect for $type {
+			fn name() -> String {
+				$name.to_string() + "<" + &T::name() + ">"
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				let (strong_offset, weak_offset, header_size) = $layout();
+				// Allocation is `#[repr(C)]`, so value follows counts, aligned
+				let value_offset = T::align().map(|align| (header_size + align - 1) / align * align);
+				let value_type_id = collector.collect::<T>();
+				let (ptr_offset, metadata_offset) =
+					pointer_offsets(size_of::<Self>(), value_type_id, collector);
+				DefType::$variant($def {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id,
+					strong_offset,
+					weak_offset,
+					value_offset,
+					ptr_offset,
+					metadata_offset,
+				})
+			}
+		}
+	};
+}
+
+shared_pointer!(Rc<T>, "Rc", Rc, DefRc, rc_allocation_layout);
+shared_pointer!(Arc<T>, "Arc", Arc, DefArc, arc_allocation_layout);
+shared_pointer!(
+	rc::Weak<T>,
+	"rc::Weak",
+	RcWeak,
+	DefRcWeak,
+	rc_allocation_layout
+);
+shared_pointer!(
+	sync::Weak<T>,
+	"sync::Weak",
+	ArcWeak,
+	DefArcWeak,
+	arc_allocation_layout
+);
+
+/// Get offsets of strong and weak counts in allocation of an `Rc` / `Arc`,
+/// and size of the counts (offset of a value with alignment 1).
+/// Order of counts is an implementation detail, so find them by inspecting
+/// an allocation with strong count 2 and weak count 1.
+macro_rules! allocation_layout {
+	($fn_name:ident, $type:ident) => {
+		fn $fn_name() -> (usize, usize, usize) {
+			let shared = $type::new(());
+			let _clone = shared.clone();
+
+			// SAFETY: `Rc<()>` / `Arc<()>` is a single pointer to allocation
+			assert_eq!(size_of::<$type<()>>(), size_of::<*const u8>());
+			let alloc_ptr: *const u8 = unsafe { transmute_copy(&shared) };
+			let header_size = $type::as_ptr(&shared) as usize - alloc_ptr as usize;
+
+			let count_offset = |count: usize| {
+				(0..header_size)
+					.step_by(size_of::<usize>())
+					.find(|&offset| {
+						// SAFETY: Counts are `usize`s within the allocation.
+						// No other references exist which could modify them.
+						let value = unsafe { (alloc_ptr.add(offset) as *const usize).read() };
+						value == count
+					})
+					.unwrap()
+			};
+			(count_offset(2), count_offset(1), header_size)
+		}
+	};
+}
+
+allocation_layout!(rc_allocation_layout, Rc);
+allocation_layout!(arc_allocation_layout, Arc);
+
+impl<T: Inspect> Inspect for Option<T> {
+	fn name() -> String {
+		"Option<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let value_type_id = collector.collect::<T>();
+		DefType::Option(DefOption {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			value_type_id,
+			niche: option_niche(value_type_id, Self::size().unwrap(), collector),
+		})
+	}
+}
+
+impl<T: Inspect, E: Inspect> Inspect for Result<T, E> {
+	fn name() -> String {
+		"Result<".to_string() + &T::name() + "," + &E::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let ok_type_id = collector.collect::<T>();
+		let err_type_id = collector.collect::<E>();
+		DefType::Result(DefResult {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			ok_type_id,
+			err_type_id,
+			niche: result_niche(ok_type_id, err_type_id, Self::size().unwrap(), collector),
+		})
+	}
+}
+
+impl<'a, B> Inspect for Cow<'a, B>
+where
+	B: Inspect + ToOwned + ?Sized + 'a,
+	B::Owned: Inspect,
+	&'a B: Inspect,
+{
+	fn name() -> String {
+		"Cow<".to_string() + &B::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let borrowed_type_id = collector.collect::<&'a B>();
+		let owned_type_id = collector.collect::<B::Owned>();
+		DefType::Cow(DefCow {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			borrowed_type_id,
+			owned_type_id,
+			niche: cow_niche(
+				borrowed_type_id,
+				owned_type_id,
+				Self::size().unwrap(),
+				collector,
+			),
+		})
+	}
+}
+
+macro_rules! transparent {
+	($name:ident, $variant:ident, $def:ident) => {
+		impl<T: Inspect> Inspect for $name<T> {
+			fn name() -> String {
+				stringify!($name).to_string() + "<" + &T::name() + ">"
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				DefType::$variant($def {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id: collector.collect::<T>(),
+				})
+			}
+		}
+	};
+}
+
+transparent!(Pin, Transparent, DefTransparent);
+transparent!(ManuallyDrop, Transparent, DefTransparent);
+transparent!(Wrapping, Transparent, DefTransparent);
+transparent!(Saturating, Transparent, DefTransparent);
+transparent!(Reverse, Transparent, DefTransparent);
+transparent!(MaybeUninit, MaybeUninit, DefMaybeUninit);
+
+impl<T: Inspect> Inspect for Range<T> {
+	fn name() -> String {
+		"Range<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let type_id = collector.collect::<T>();
+		DefType::Struct(DefStruct {
+			name: Self::name(),
+			ser_name: "Range".to_string(),
+			size: Self::size(),
+			align: Self::align(),
+			fields: vec![
+				range_field("start", type_id, offset_of!(Range<T>, start), false),
+				range_field("end", type_id, offset_of!(Range<T>, end), false),
+			],
+			tag: None,
+			transparent: false,
+			repr: DefRepr::default(),
+		})
+	}
+}
+
+// Fields of `RangeInclusive` are private, so offsets are found from an
+// instance. `Default` is required to create one.
+impl<T: Inspect + Default> Inspect for RangeInclusive<T> {
+	fn name() -> String {
+		"RangeInclusive<".to_string() + &T::name() + ">"
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(collector: &mut TypesCollector) -> DefType {
+		let type_id = collector.collect::<T>();
+		let (start_offset, end_offset, exhausted_offset) = range_inclusive_offsets::<T>();
+		DefType::Struct(DefStruct {
+			name: Self::name(),
+			ser_name: "RangeInclusive".to_string(),
+			size: Self::size(),
+			align: Self::align(),
+			fields: vec![
+				range_field("start", type_id, start_offset, false),
+				range_field("end", type_id, end_offset, false),
+				// Set once iteration is complete. Not serialized.
+				range_field(
+					"exhausted",
+					collector.collect::<bool>(),
+					exhausted_offset,
+					true,
+				),
+			],
+			tag: None,
+			transparent: false,
+			repr: DefRepr::default(),
+		})
+	}
+}
+
+fn range_field(name: &str, type_id: TypeId, offset: usize, skip: bool) -> DefStructField {
+	DefStructField {
+		name: name.to_string(),
+		ser_name: name.to_string(),
+		type_id,
+		offset,
+		flatten: false,
+		skip,
+	}
+}
+
+/// Get offsets of `start`, `end` and `exhausted` within a `RangeInclusive`.
+/// `exhausted` is a `bool`, so has alignment 1 and is followed by any padding.
+/// It is therefore at the first byte not covered by `start` or `end`.
+fn range_inclusive_offsets<T: Default>() -> (usize, usize, usize) {
+	let range = RangeInclusive::new(T::default(), T::default());
+	let range_ptr = &range as *const _ as usize;
+	let start_offset = range.start() as *const T as usize - range_ptr;
+	let end_offset = range.end() as *const T as usize - range_ptr;
+
+	let covered = |offset: usize, field_offset: usize| {
+		(field_offset..field_offset + size_of::<T>()).contains(&offset)
+	};
+	let exhausted_offset = (0..size_of::<RangeInclusive<T>>())
+		.find(|&offset| !covered(offset, start_offset) && !covered(offset, end_offset))
+		.unwrap();
+	(start_offset, end_offset, exhausted_offset)
+}
+
+impl Inspect for Duration {
+	fn name() -> String {
+		"Duration".to_string()
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(_collector: &mut TypesCollector) -> DefType {
+		let (secs_offset, nanos_offset) = duration_offsets();
+		DefType::Duration(DefDuration {
+			name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			secs_offset,
+			nanos_offset,
+		})
+	}
+}
+
+/// Get offsets of seconds and nanoseconds within a `Duration`.
+/// Fields are private, so find them by inspecting a `Duration` with known
+/// values. Returns `None`s if layout is not as expected.
+fn duration_offsets() -> (Option<usize>, Option<usize>) {
+	const SECS: u64 = 0x0123_4567_89ab_cdef;
+	const NANOS: u32 = 999_999_999;
+	let duration = Duration::new(SECS, NANOS);
+	let duration_ptr = &duration as *const Duration as *const u8;
+
+	// There is no padding before first field or between the 2 fields,
+	// so scanning from start and stopping at first match never reads padding
+	let nanos_offset = (0..size_of::<Duration>() / size_of::<u32>())
+		.map(|index| index * size_of::<u32>())
+		.find(|&offset| {
+			// SAFETY: Offset is within the `Duration`, and not after nanoseconds
+			let value = unsafe { (duration_ptr.add(offset) as *const u32).read_unaligned() };
+			value == NANOS
+		});
+	let Some(nanos_offset) = nanos_offset else {
+		return (None, None);
+	};
+
+	let secs_offset = if nanos_offset == 0 {
+		(size_of::<u32>() + align_of::<u64>() - 1) / align_of::<u64>() * align_of::<u64>()
+	} else {
+		0
+	};
+	if secs_offset + size_of::<u64>() > size_of::<Duration>() {
+		return (None, None);
+	}
+	// SAFETY: Checked within bounds. Seconds is the only other field.
+	let secs = unsafe { (duration_ptr.add(secs_offset) as *const u64).read_unaligned() };
+	if secs == SECS {
+		(Some(secs_offset), Some(nanos_offset))
+	} else {
+		(None, None)
+	}
+}
+
+macro_rules! opaque {
+	($type:ident, $logical_type:ident) => {
+		impl Inspect for $type {
+			fn name() -> String {
+				stringify!($type).to_string()
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(_collector: &mut TypesCollector) -> DefType {
+				DefType::Opaque(DefOpaque {
+					name: Self::name(),
+					size: Self::size(),
+					align: Self::align(),
+					logical_type: DefLogicalType::$logical_type,
+				})
+			}
+		}
+	};
+	// Unsized types. Alignment is obtained from an example value.
+	($type:ident, $logical_type:ident, $example:expr) => {
+		impl Inspect for $type {
+			fn name() -> String {
+				stringify!($type).to_string()
+			}
+
+			fn size() -> Option<usize> {
+				None
+			}
+
+			fn align() -> Option<usize> {
+				let example: &$type = $example;
+				Some(align_of_val(example))
+			}
+
+			fn def(_collector: &mut TypesCollector) -> DefType {
+				DefType::Opaque(DefOpaque {
+					name: Self::name(),
+					size: Self::size(),
+					align: Self::align(),
+					logical_type: DefLogicalType::$logical_type,
+				})
+			}
+		}
+
+		opaque_ref!($type, "&", false, &'a);
+		opaque_ref!($type, "&mut ", true, &'a mut);
+	};
+}
+
+macro_rules! opaque_ref {
+	($type:ident, $prefix:literal, $mutable:literal, $($ref:tt)+) => {
+		impl<'a> Inspect for $($ref)+ $type {
+			fn name() -> String {
+				$prefix.to_string() + &$type::name()
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				let value_type_id = collector.collect::<$type>();
+				let (ptr_offset, metadata_offset) =
+					pointer_offsets(size_of::<Self>(), value_type_id, collector);
+				DefType::Ref(DefRef {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					value_type_id,
+					mutable: $mutable,
+					fat: true,
+					ptr_offset,
+					metadata_offset,
+				})
+			}
+		}
+	};
+}
+
+opaque!(PathBuf, Path);
+opaque!(Path, Path, Path::new(""));
+opaque!(OsString, OsStr);
+opaque!(OsStr, OsStr, OsStr::new(""));
+opaque!(CString, CStr);
+opaque!(CStr, CStr, Default::default());
+opaque!(Instant, Instant);
+opaque!(SystemTime, SystemTime);
+opaque!(IpAddr, IpAddr);
+opaque!(Ipv4Addr, Ipv4Addr);
+opaque!(Ipv6Addr, Ipv6Addr);
+opaque!(SocketAddr, SocketAddr);
+opaque!(SocketAddrV4, SocketAddrV4);
+opaque!(SocketAddrV6, SocketAddrV6);
+
+impl Inspect for cmp::Ordering {
+	fn name() -> String {
+		"Ordering".to_string()
+	}
+
+	fn size() -> Option<usize> {
+		Some(size_of::<Self>())
+	}
+
+	fn align() -> Option<usize> {
+		Some(align_of::<Self>())
+	}
+
+	fn def(_collector: &mut TypesCollector) -> DefType {
+		// `#[repr(i8)]` fieldless enum
+		let variant = |name: &str, value: cmp::Ordering| {
+			DefEnumVariant {
+				name: name.to_string(),
+				discriminant: value as i128,
+				ser_value: Some(name.to_string()),
+				payload: DefEnumVariantPayload::Unit,
+				payload_offset: None,
+			}
+		};
+		DefType::Enum(DefEnum {
+			name: Self::name(),
+			ser_name: Self::name(),
+			size: Self::size().unwrap(),
+			align: Self::align().unwrap(),
+			variants: vec![
+				variant("Less", cmp::Ordering::Less),
+				variant("Equal", cmp::Ordering::Equal),
+				variant("Greater", cmp::Ordering::Greater),
+			],
+			tag: DefEnumTag::None,
+			repr: DefRepr {
+				int: Some("i8".to_string()),
+				..DefRepr::default()
+			},
+			tag_layout: Some(DefEnumTagLayout {
+				offset: 0,
+				size: size_of::<i8>(),
+			}),
+			niche: None,
+		})
+	}
+}
+
+macro_rules! tuple {
+	($($index:tt $param:ident),+) => {
+		impl<$($param: Inspect),+> Inspect for ($($param,)+) {
+			fn name() -> String {
+				let names = [$($param::name()),+];
+				// Single-element tuples are written `(T,)`
+				let trailing = if names.len() == 1 { ",)" } else { ")" };
+				"(".to_string() + &names.join(",") + trailing
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				// Offsets are obtained from compiler as tuple fields can be reordered
+				DefType::Tuple(DefTuple {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					elements: vec![$(
+						DefTupleElement {
+							type_id: collector.collect::<$param>(),
+							offset: offset_of_tuple!(Self, $index),
+						}
+					),+],
+				})
+			}
+		}
+	};
+}
+
+tuple!(0 T0);
+tuple!(0 T0, 1 T1);
+tuple!(0 T0, 1 T1, 2 T2);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10);
+tuple!(0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10, 11 T11);
+
+macro_rules! fn_ptr {
+	($($param:ident),*) => {
+		fn_ptr!(@impl "", "Rust", false, fn($($param),*) -> R, $($param),*);
+		fn_ptr!(@impl "unsafe ", "Rust", true, unsafe fn($($param),*) -> R, $($param),*);
+		fn_ptr!(@impl "extern \"C\" ", "C", false, extern "C" fn($($param),*) -> R, $($param),*);
+		fn_ptr!(
+			@impl "unsafe extern \"C\" ", "C", true,
+			unsafe extern "C" fn($($param),*) -> R, $($param),*
+		);
+	};
+	(@impl $prefix:literal, $abi:literal, $unsafe:literal, $type:ty, $($param:ident),*) => {
+		impl<R: Inspect, $($param: Inspect),*> Inspect for $type {
+			fn name() -> String {
+				let names: Vec<String> = vec![$($param::name()),*];
+				let rtn = R::name();
+				// Functions returning `()` are written without return type
+				let rtn = if rtn == "()" { String::new() } else { " -> ".to_string() + &rtn };
+				$prefix.to_string() + "fn(" + &names.join(",") + ")" + &rtn
+			}
+
+			fn size() -> Option<usize> {
+				Some(size_of::<Self>())
+			}
+
+			fn align() -> Option<usize> {
+				Some(align_of::<Self>())
+			}
+
+			fn def(collector: &mut TypesCollector) -> DefType {
+				DefType::FnPtr(DefFnPtr {
+					name: Self::name(),
+					size: Self::size().unwrap(),
+					align: Self::align().unwrap(),
+					arg_type_ids: vec![$(collector.collect::<$param>()),*],
+					return_type_id: collector.collect::<R>(),
+					abi: $abi.to_string(),
+					is_unsafe: $unsafe,
 				})
 			}
 		}
 	};
 }
 
-double_type_param!(Result, DefResult, ok_type_id, err_type_id);
+fn_ptr!();
+fn_ptr!(A0);
+fn_ptr!(A0, A1);
+fn_ptr!(A0, A1, A2);
+fn_ptr!(A0, A1, A2, A3);
+fn_ptr!(A0, A1, A2, A3, A4);
+fn_ptr!(A0, A1, A2, A3, A4, A5);
+fn_ptr!(A0, A1, A2, A3, A4, A5, A6);
+fn_ptr!(A0, A1, A2, A3, A4, A5, A6, A7);
+fn_ptr!(A0, A1, A2, A3, A4, A5, A6, A7, A8);
+fn_ptr!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9);
+fn_ptr!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+fn_ptr!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
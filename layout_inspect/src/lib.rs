@@ -1,19 +1,32 @@
-#![cfg_attr(feature = "nightly", feature(core_intrinsics))]
+#![cfg_attr(feature = "nightly", feature(core_intrinsics, never_type))]
 
 use std::collections::hash_map::HashMap;
 
 #[cfg(feature = "derive")]
 pub use layout_inspect_derive::Inspect;
-// Used by `Inspect` derive macro
-#[cfg(feature = "derive")]
+// Used by `Inspect` derive macro and `inspect_dyn!`
 #[doc(hidden)]
 pub mod __private {
+	#[cfg(feature = "derive")]
 	pub use memoffset;
+
+	#[cfg(feature = "derive")]
+	pub use crate::enum_layout::{complete_enum_layout, DefaultProbe, ProbeDefault, ProbeNone};
+	#[cfg(feature = "derive")]
+	pub use crate::unsized_alloc::UnsizedAlloc;
+	pub use crate::{dyn_trait::dyn_ptr_offsets, impls::pointer_offsets};
 }
 
 pub mod defs;
+mod dyn_trait;
+#[cfg(feature = "derive")]
+mod enum_layout;
+mod external;
 mod impls;
+mod niche;
 mod primitives;
+#[cfg(feature = "derive")]
+mod unsized_alloc;
 use defs::DefType;
 
 pub type TypeId = u32;
@@ -24,17 +37,8 @@ pub fn inspect<T: Inspect + ?Sized>() -> Vec<DefType> {
 	collector.into_types()
 }
 
-#[cfg(not(feature = "unique_names"))]
-// `'static` bound required by `any::TypeId::of`
-pub trait Inspect: 'static {
-	fn name() -> String;
-	fn size() -> Option<usize>;
-	fn align() -> Option<usize>;
-	fn def(collector: &mut TypesCollector) -> DefType;
-}
-
-#[cfg(feature = "unique_names")]
-// `any::TypeId::of` not used, so no need for `'static` bound
+// No `'static` bound, so types with lifetimes can be inspected.
+// `type_id_of` erases lifetimes before obtaining native type ID.
 pub trait Inspect {
 	fn name() -> String;
 	fn size() -> Option<usize>;
@@ -72,6 +76,10 @@ impl TypesCollector {
 		}
 	}
 
+	fn get(&self, id: TypeId) -> Option<&DefType> {
+		self.types[id as usize].as_ref()
+	}
+
 	fn into_types(self) -> Vec<DefType> {
 		self
 			.types
@@ -87,8 +95,35 @@ compile_error!("stable and nightly features cannot both be enabled");
 #[cfg(not(any(feature = "stable", feature = "nightly")))]
 compile_error!("either stable or nightly feature must be enabled");
 
+/// Get native type ID for a type which may not be `'static`.
+/// `any::TypeId::of` requires `'static`, but lifetimes do not affect type IDs,
+/// so erase them by calling via a trait object with lifetime extended to
+/// `'static`. Same technique as `typeid` crate.
+#[cfg(not(feature = "unique_names"))]
+fn type_id_of<T: ?Sized>() -> String {
+	use std::{marker::PhantomData, mem};
+
+	trait NonStaticAny {
+		fn native_type_id(&self) -> String
+		where Self: 'static;
+	}
+
+	impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+		fn native_type_id(&self) -> String
+		where Self: 'static {
+			static_type_id_of::<T>()
+		}
+	}
+
+	let phantom = PhantomData::<T>;
+	let phantom: &dyn NonStaticAny = &phantom;
+	// SAFETY: `native_type_id` does not access any data with the extended lifetime
+	let phantom: &(dyn NonStaticAny + 'static) = unsafe { mem::transmute(phantom) };
+	phantom.native_type_id()
+}
+
 #[cfg(all(feature = "nightly", not(feature = "unique_names")))]
-fn type_id_of<T: 'static + ?Sized>() -> String {
+fn static_type_id_of<T: 'static + ?Sized>() -> String {
 	use std::intrinsics::type_id;
 	type_id::<T>().to_string()
 }
@@ -98,7 +133,7 @@ fn type_id_of<T: 'static + ?Sized>() -> String {
 	not(feature = "nightly"),
 	not(feature = "unique_names")
 ))]
-fn type_id_of<T: 'static + ?Sized>() -> String {
+fn static_type_id_of<T: 'static + ?Sized>() -> String {
 	// Hacky way to get Rust's native type ID without nightly.
 	// `std::any::TypeId` does not expose any direct way to get the actual u128 ID.
 	use std::any;
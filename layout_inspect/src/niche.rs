@@ -0,0 +1,492 @@
+use std::{
+	mem::{align_of, size_of},
+	time::Duration,
+};
+
+use crate::{
+	defs::{
+		DefArc, DefArcWeak, DefBox, DefDuration, DefEnum, DefNiche, DefNicheValue, DefPrimitive, DefRc,
+		DefRcWeak, DefRef, DefSliceRef, DefStrSlice, DefString, DefType, DefVec,
+	},
+	TypeId, TypesCollector,
+};
+
+// Niches are invalid values of a type (e.g. `2` for a `bool`, `0` for a `&T`).
+// Enums, `Option` and `Result` can store which variant they are in a niche
+// of one variant's fields, instead of in a separate tag.
+//
+// Layout of enums with default repr is not defined, so logic here mirrors
+// rustc's niche selection and reservation.
+
+/// Invalid values of a type available for use as a niche.
+/// `size` bytes at `offset`, where only values `start..=end` are valid.
+/// Range of valid values wraps around if `start > end`.
+#[derive(Clone, Copy)]
+struct Niche {
+	offset: usize,
+	size: usize,
+	start: u128,
+	end: u128,
+}
+
+impl Niche {
+	fn max_value(&self) -> u128 {
+		if self.size >= 16 {
+			u128::MAX
+		} else {
+			(1 << (self.size * 8)) - 1
+		}
+	}
+
+	fn available(&self) -> u128 {
+		self.start.wrapping_sub(self.end).wrapping_sub(1) & self.max_value()
+	}
+
+	fn with_offset(self, offset: usize) -> Self {
+		Niche {
+			offset: self.offset + offset,
+			..self
+		}
+	}
+
+	/// Reserve `count` invalid values.
+	/// Returns first reserved value, and niche remaining after reservation.
+	/// Same logic as rustc's `Niche::reserve`.
+	fn reserve(&self, count: u128) -> Option<(u128, Niche)> {
+		if count > self.available() {
+			return None;
+		}
+
+		let max_value = self.max_value();
+		let move_start = || {
+			let start = self.start.wrapping_sub(count) & max_value;
+			(start, Niche { start, ..*self })
+		};
+		let move_end = || {
+			let first = self.end.wrapping_add(1) & max_value;
+			let end = self.end.wrapping_add(count) & max_value;
+			(first, Niche { end, ..*self })
+		};
+
+		let reserved = if self.start > self.end {
+			move_end()
+		} else if self.start <= max_value - self.end {
+			if count <= self.start {
+				move_start()
+			} else {
+				move_end()
+			}
+		} else {
+			let end = self.end.wrapping_add(count) & max_value;
+			if (1..=self.end).contains(&end) {
+				move_start()
+			} else {
+				move_end()
+			}
+		};
+		Some(reserved)
+	}
+}
+
+/// Get niche used by `Option<T>` to store `None`
+pub(crate) fn option_niche(
+	value_type_id: TypeId,
+	size: usize,
+	collector: &TypesCollector,
+) -> Option<DefNiche> {
+	option_niche_and_remainder(value_type_id, size, collector).map(|(niche, _)| niche)
+}
+
+/// Get niche used by `Result<T, E>` to store the smaller variant
+pub(crate) fn result_niche(
+	ok_type_id: TypeId,
+	err_type_id: TypeId,
+	size: usize,
+	collector: &TypesCollector,
+) -> Option<DefNiche> {
+	result_niche_and_remainder(ok_type_id, err_type_id, size, collector).map(|(niche, _)| niche)
+}
+
+/// Get niche used by `Cow<'a, B>` to store the smaller variant
+pub(crate) fn cow_niche(
+	borrowed_type_id: TypeId,
+	owned_type_id: TypeId,
+	size: usize,
+	collector: &TypesCollector,
+) -> Option<DefNiche> {
+	cow_niche_and_remainder(borrowed_type_id, owned_type_id, size, collector).map(|(niche, _)| niche)
+}
+
+/// Get niche used by an enum to store its dataless variants
+#[cfg(feature = "derive")]
+pub(crate) fn enum_niche(def: &DefEnum, collector: &TypesCollector) -> Option<DefNiche> {
+	enum_niche_and_remainder(def, collector).map(|(niche, _)| niche)
+}
+
+/// Find largest niche in a type.
+/// Where niches are equal size, the one at lowest offset is chosen.
+fn largest_niche(type_id: TypeId, collector: &TypesCollector) -> Option<Niche> {
+	match collector.get(type_id)? {
+		DefType::Primitive(def) => primitive_niche(def),
+		DefType::Struct(def) => {
+			fields_niche(
+				def
+					.fields
+					.iter()
+					.map(|field| (field.type_id, Some(field.offset))),
+				collector,
+			)
+		}
+		DefType::Tuple(def) => {
+			fields_niche(
+				def
+					.elements
+					.iter()
+					.map(|element| (element.type_id, Some(element.offset))),
+				collector,
+			)
+		}
+		DefType::Array(def) if def.len > 0 => largest_niche(def.value_type_id, collector),
+		DefType::Box(DefBox { ptr_offset, .. })
+		| DefType::Rc(DefRc { ptr_offset, .. })
+		| DefType::Arc(DefArc { ptr_offset, .. })
+		| DefType::RcWeak(DefRcWeak { ptr_offset, .. })
+		| DefType::ArcWeak(DefArcWeak { ptr_offset, .. })
+		| DefType::Ref(DefRef { ptr_offset, .. })
+		| DefType::StrSlice(DefStrSlice { ptr_offset, .. })
+		| DefType::SliceRef(DefSliceRef { ptr_offset, .. }) => Some(pointer_niche(*ptr_offset)),
+		DefType::Ptr(def) if def.non_null => Some(pointer_niche(def.ptr_offset)),
+		DefType::FnPtr(_) => Some(pointer_niche(0)),
+		DefType::Option(def) => {
+			option_niche_and_remainder(def.value_type_id, def.size, collector)
+				.map(|(_, remainder)| remainder)
+				.or_else(|| {
+					let value_def = collector.get(def.value_type_id)?;
+					Some(tag_niche(tag_size(&[value_def]), 0, 1))
+				})
+		}
+		DefType::Result(def) => {
+			result_niche_and_remainder(def.ok_type_id, def.err_type_id, def.size, collector)
+				.map(|(_, remainder)| remainder)
+				.or_else(|| {
+					let ok_def = collector.get(def.ok_type_id)?;
+					let err_def = collector.get(def.err_type_id)?;
+					Some(tag_niche(tag_size(&[ok_def, err_def]), 0, 1))
+				})
+		}
+		DefType::Cow(def) => {
+			cow_niche_and_remainder(def.borrowed_type_id, def.owned_type_id, def.size, collector)
+				.map(|(_, remainder)| remainder)
+				.or_else(|| {
+					let borrowed_def = collector.get(def.borrowed_type_id)?;
+					let owned_def = collector.get(def.owned_type_id)?;
+					Some(tag_niche(tag_size(&[borrowed_def, owned_def]), 0, 1))
+				})
+		}
+		DefType::Transparent(def) => largest_niche(def.value_type_id, collector),
+		DefType::Duration(DefDuration {
+			nanos_offset: Some(nanos_offset),
+			..
+		}) => duration_niche(*nanos_offset),
+		DefType::Enum(def) => {
+			if let Some(tag_layout) = &def.tag_layout {
+				let discriminants = def.variants.iter().map(|variant| variant.discriminant);
+				let min = discriminants.clone().min()?;
+				let max = discriminants.max()?;
+				let niche = tag_niche(tag_layout.size, min as u128, max as u128);
+				Some(niche.with_offset(tag_layout.offset))
+			} else {
+				enum_niche_and_remainder(def, collector).map(|(_, remainder)| remainder)
+			}
+		}
+		DefType::Vec(DefVec {
+			ptr_offset,
+			cap_offset,
+			..
+		})
+		| DefType::String(DefString {
+			ptr_offset,
+			cap_offset,
+			..
+		}) => Some(vec_niche(*ptr_offset, *cap_offset)),
+		_ => None,
+	}
+}
+
+/// Niche of a primitive is values outside its valid ranges.
+/// Gaps between ranges (e.g. surrogates in `char`) are not used as niches.
+fn primitive_niche(def: &DefPrimitive) -> Option<Niche> {
+	Some(Niche {
+		offset: 0,
+		size: def.size,
+		start: def.valid_ranges.first()?.start,
+		end: def.valid_ranges.last()?.end,
+	})
+}
+
+fn pointer_niche(offset: usize) -> Niche {
+	Niche {
+		offset,
+		size: size_of::<usize>(),
+		start: 1,
+		end: usize::MAX as u128,
+	}
+}
+
+/// Niche of a `Vec` or `String`.
+/// Newer versions of std restrict capacity to `0..=isize::MAX`, which gives a
+/// larger niche than the pointer. Detect this by whether `Vec` has room for
+/// 2 niche values.
+fn vec_niche(ptr_offset: usize, cap_offset: usize) -> Niche {
+	if size_of::<Option<Option<Vec<u8>>>>() == size_of::<Vec<u8>>() {
+		Niche {
+			offset: cap_offset,
+			size: size_of::<usize>(),
+			start: 0,
+			end: isize::MAX as u128,
+		}
+	} else {
+		pointer_niche(ptr_offset)
+	}
+}
+
+/// Niche of a `Duration`'s nanoseconds, which are always less than 1 second.
+/// Older versions of std do not declare this range to the compiler.
+fn duration_niche(nanos_offset: usize) -> Option<Niche> {
+	if size_of::<Option<Duration>>() != size_of::<Duration>() {
+		return None;
+	}
+	Some(Niche {
+		offset: nanos_offset,
+		size: size_of::<u32>(),
+		start: 0,
+		end: 999_999_999,
+	})
+}
+
+/// Niche of a tag with values `min..=max`.
+/// Negative values are truncated to size of tag, so range may wrap around.
+fn tag_niche(size: usize, min: u128, max: u128) -> Niche {
+	let niche = Niche {
+		offset: 0,
+		size,
+		start: min,
+		end: max,
+	};
+	Niche {
+		start: min & niche.max_value(),
+		end: max & niche.max_value(),
+		..niche
+	}
+}
+
+/// Size of tag for a tagged enum, given defs of first field in each variant.
+/// rustc widens tag to alignment of the fields which follow it.
+fn tag_size(first_field_defs: &[&DefType]) -> usize {
+	let align = first_field_defs
+		.iter()
+		.filter(|def| !(def.size() == Some(0) && def.align() == Some(1)))
+		.filter_map(|def| def.align())
+		.min();
+	match align {
+		Some(align @ (1 | 2 | 4 | 8)) => align,
+		Some(16) if align_of::<u128>() == 16 => 16,
+		_ => 1,
+	}
+}
+
+/// Find largest niche in a set of fields, given their type IDs and offsets.
+/// If offset of any non-zero-sized field is unknown, returns `None`.
+fn fields_niche(
+	fields: impl Iterator<Item = (TypeId, Option<usize>)>,
+	collector: &TypesCollector,
+) -> Option<Niche> {
+	let mut fields = fields
+		.map(|(type_id, offset)| {
+			let size = collector.get(type_id)?.size()?;
+			Some((type_id, offset, size))
+		})
+		.collect::<Option<Vec<_>>>()?;
+	fields.retain(|&(_, _, size)| size > 0);
+
+	// Field's offset is unknown, unless it's the only non-zero-sized field
+	let single = fields.len() == 1;
+	let mut fields = fields
+		.into_iter()
+		.map(|(type_id, offset, _)| {
+			match offset {
+				Some(offset) => Some((type_id, offset)),
+				None if single => Some((type_id, 0)),
+				None => None,
+			}
+		})
+		.collect::<Option<Vec<_>>>()?;
+	fields.sort_by_key(|&(_, offset)| offset);
+
+	let mut largest: Option<Niche> = None;
+	for (type_id, offset) in fields {
+		if let Some(niche) = largest_niche(type_id, collector) {
+			if largest.map_or(true, |largest| niche.available() > largest.available()) {
+				largest = Some(niche.with_offset(offset));
+			}
+		}
+	}
+	largest
+}
+
+fn option_niche_and_remainder(
+	value_type_id: TypeId,
+	size: usize,
+	collector: &TypesCollector,
+) -> Option<(DefNiche, Niche)> {
+	// `Option<T>` uses a niche if it's no larger than `T`
+	if collector.get(value_type_id)?.size()? != size {
+		return None;
+	}
+
+	let niche = largest_niche(value_type_id, collector)?;
+	let (value, remainder) = niche.reserve(1)?;
+	let def = DefNiche {
+		offset: niche.offset,
+		size: niche.size,
+		untagged_variant: "Some".to_string(),
+		values: vec![DefNicheValue {
+			variant: "None".to_string(),
+			value,
+		}],
+	};
+	Some((def, remainder))
+}
+
+fn result_niche_and_remainder(
+	ok_type_id: TypeId,
+	err_type_id: TypeId,
+	size: usize,
+	collector: &TypesCollector,
+) -> Option<(DefNiche, Niche)> {
+	two_variant_niche_and_remainder(("Ok", ok_type_id), ("Err", err_type_id), size, collector)
+}
+
+fn cow_niche_and_remainder(
+	borrowed_type_id: TypeId,
+	owned_type_id: TypeId,
+	size: usize,
+	collector: &TypesCollector,
+) -> Option<(DefNiche, Niche)> {
+	two_variant_niche_and_remainder(
+		("Borrowed", borrowed_type_id),
+		("Owned", owned_type_id),
+		size,
+		collector,
+	)
+}
+
+/// Niche of an enum with 2 variants, each with a single field
+/// e.g. `Result<T, E>`, `Cow<'a, B>`
+fn two_variant_niche_and_remainder(
+	(first_variant, first_type_id): (&str, TypeId),
+	(second_variant, second_type_id): (&str, TypeId),
+	size: usize,
+	collector: &TypesCollector,
+) -> Option<(DefNiche, Niche)> {
+	// Larger variant is untagged. Where equal size, second is untagged.
+	let first_size = collector.get(first_type_id)?.size()?;
+	let second_size = collector.get(second_type_id)?.size()?;
+	let (untagged_type_id, untagged_size, untagged_variant, other_variant) =
+		if first_size > second_size {
+			(first_type_id, first_size, first_variant, second_variant)
+		} else {
+			(second_type_id, second_size, second_variant, first_variant)
+		};
+
+	// Enum uses a niche if it's no larger than untagged variant
+	if untagged_size != size {
+		return None;
+	}
+
+	let niche = largest_niche(untagged_type_id, collector)?;
+	let (value, remainder) = niche.reserve(1)?;
+	let def = DefNiche {
+		offset: niche.offset,
+		size: niche.size,
+		untagged_variant: untagged_variant.to_string(),
+		values: vec![DefNicheValue {
+			variant: other_variant.to_string(),
+			value,
+		}],
+	};
+	Some((def, remainder))
+}
+
+fn enum_niche_and_remainder(
+	def: &DefEnum,
+	collector: &TypesCollector,
+) -> Option<(DefNiche, Niche)> {
+	// Enums with `#[repr(C)]` / `#[repr(u8)]` etc, or with a tag, do not use a
+	// niche
+	if def.repr.c || def.repr.int.is_some() || def.tag_layout.is_some() || def.variants.len() < 2 {
+		return None;
+	}
+
+	// Largest variant is untagged. Where equal size, last is untagged.
+	// rustc orders fields of each variant to avoid padding, so size of variant
+	// is sum of field sizes, rounded up to alignment.
+	let mut untagged: Option<(usize, usize)> = None;
+	for (index, variant) in def.variants.iter().enumerate() {
+		let mut size = 0;
+		let mut align = 1;
+		for field in variant.payload.fields() {
+			let field_def = collector.get(field.type_id)?;
+			size += field_def.size()?;
+			align = align.max(field_def.align()?);
+		}
+		let size = (size + align - 1) / align * align;
+		if untagged.map_or(true, |(_, untagged_size)| size >= untagged_size) {
+			untagged = Some((index, size));
+		}
+	}
+	let (untagged_index, untagged_size) = untagged?;
+
+	// Enum uses a niche if it's no larger than untagged variant
+	if untagged_size != def.size {
+		return None;
+	}
+
+	let untagged_variant = &def.variants[untagged_index];
+	let niche = fields_niche(
+		untagged_variant
+			.payload
+			.fields()
+			.iter()
+			.map(|field| (field.type_id, field.offset)),
+		collector,
+	)?;
+
+	// Values are reserved for every variant from first to last non-untagged variant
+	let first_index = if untagged_index == 0 { 1 } else { 0 };
+	let last_index = if untagged_index == def.variants.len() - 1 {
+		untagged_index - 1
+	} else {
+		def.variants.len() - 1
+	};
+	let count = (last_index - first_index + 1) as u128;
+	let (first_value, remainder) = niche.reserve(count)?;
+
+	let values = (first_index..=last_index)
+		.filter(|&index| index != untagged_index)
+		.map(|index| {
+			DefNicheValue {
+				variant: def.variants[index].name.clone(),
+				value: first_value.wrapping_add((index - first_index) as u128) & niche.max_value(),
+			}
+		})
+		.collect();
+
+	let def = DefNiche {
+		offset: niche.offset,
+		size: niche.size,
+		untagged_variant: untagged_variant.name.clone(),
+		values,
+	};
+	Some((def, remainder))
+}
@@ -1,4 +1,5 @@
 use std::{
+	convert::Infallible,
 	mem::{align_of, size_of},
 	num::{
 		NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
@@ -7,12 +8,15 @@ use std::{
 };
 
 use crate::{
-	defs::{DefPrimitive, DefType},
+	defs::{DefPrimitive, DefPrimitiveKind, DefType, DefValidRange},
 	Inspect, TypesCollector,
 };
 
 macro_rules! primitive {
-	($type:ty) => {
+	($type:ty, $kind:ident) => {
+		primitive!($type, $kind, all_bit_patterns(size_of::<Self>()));
+	};
+	($type:ty, $kind:ident, $valid_ranges:expr) => {
 		impl Inspect for $type {
 			fn name() -> String {
 				stringify!($type).to_string()
@@ -31,44 +35,89 @@ macro_rules! primitive {
 					name: Self::name(),
 					size: Self::size().unwrap(),
 					align: Self::align().unwrap(),
+					kind: DefPrimitiveKind::$kind,
+					valid_ranges: $valid_ranges,
 				})
 			}
 		}
 	};
 }
 
-primitive!(u8);
-primitive!(u16);
-primitive!(u32);
-primitive!(u64);
-primitive!(u128);
-primitive!(usize);
-
-primitive!(i8);
-primitive!(i16);
-primitive!(i32);
-primitive!(i64);
-primitive!(i128);
-primitive!(isize);
-
-primitive!(NonZeroU8);
-primitive!(NonZeroU16);
-primitive!(NonZeroU32);
-primitive!(NonZeroU64);
-primitive!(NonZeroU128);
-primitive!(NonZeroUsize);
-
-primitive!(NonZeroI8);
-primitive!(NonZeroI16);
-primitive!(NonZeroI32);
-primitive!(NonZeroI64);
-primitive!(NonZeroI128);
-primitive!(NonZeroIsize);
-
-primitive!(f32);
-primitive!(f64);
-
-primitive!(bool);
-primitive!(char);
-
-primitive!(());
+primitive!(u8, UnsignedInt);
+primitive!(u16, UnsignedInt);
+primitive!(u32, UnsignedInt);
+primitive!(u64, UnsignedInt);
+primitive!(u128, UnsignedInt);
+primitive!(usize, UnsignedInt);
+
+primitive!(i8, SignedInt);
+primitive!(i16, SignedInt);
+primitive!(i32, SignedInt);
+primitive!(i64, SignedInt);
+primitive!(i128, SignedInt);
+primitive!(isize, SignedInt);
+
+primitive!(NonZeroU8, UnsignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroU16, UnsignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroU32, UnsignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroU64, UnsignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroU128, UnsignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroUsize, UnsignedInt, non_zero(size_of::<Self>()));
+
+primitive!(NonZeroI8, SignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroI16, SignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroI32, SignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroI64, SignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroI128, SignedInt, non_zero(size_of::<Self>()));
+primitive!(NonZeroIsize, SignedInt, non_zero(size_of::<Self>()));
+
+primitive!(f32, Float);
+primitive!(f64, Float);
+
+primitive!(bool, Bool, vec![DefValidRange { start: 0, end: 1 }]);
+primitive!(
+	char,
+	Char,
+	// Surrogates are not valid `char`s
+	vec![
+		DefValidRange {
+			start: 0,
+			end: 0xd7ff
+		},
+		DefValidRange {
+			start: 0xe000,
+			end: char::MAX as u128,
+		},
+	]
+);
+
+primitive!((), Unit);
+
+// Never type is uninhabited, so has no valid values
+primitive!(Infallible, Never, vec![]);
+#[cfg(feature = "nightly")]
+primitive!(!, Never, vec![]);
+
+/// All bit patterns are valid
+fn all_bit_patterns(size: usize) -> Vec<DefValidRange> {
+	vec![DefValidRange {
+		start: 0,
+		end: max_value(size),
+	}]
+}
+
+/// All bit patterns except 0 are valid
+fn non_zero(size: usize) -> Vec<DefValidRange> {
+	vec![DefValidRange {
+		start: 1,
+		end: max_value(size),
+	}]
+}
+
+fn max_value(size: usize) -> u128 {
+	if size >= 16 {
+		u128::MAX
+	} else {
+		(1 << (size * 8)) - 1
+	}
+}
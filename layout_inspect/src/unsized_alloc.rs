@@ -0,0 +1,37 @@
+use std::alloc::{alloc_zeroed, dealloc, handle_alloc_error, Layout};
+
+/// Zeroed heap allocation, freed on drop.
+///
+/// Used by `Inspect` derive macro to obtain a pointer to a zero-length instance
+/// of a struct whose last field is `str` or a slice. Offsets of the struct's
+/// fields are measured from that pointer with `ptr::addr_of!`, which
+/// `memoffset::offset_of!` can't do for unsized types.
+pub struct UnsizedAlloc {
+	ptr: *mut u8,
+	layout: Layout,
+}
+
+impl UnsizedAlloc {
+	/// Allocate `size` bytes with alignment `align`.
+	/// `size` must be at least size of the struct's sized fields plus padding.
+	pub fn new(size: usize, align: usize) -> Self {
+		let layout = Layout::from_size_align(size.max(1), align).unwrap();
+		// SAFETY: `layout` has non-zero size
+		let ptr = unsafe { alloc_zeroed(layout) };
+		if ptr.is_null() {
+			handle_alloc_error(layout);
+		}
+		Self { ptr, layout }
+	}
+
+	pub fn as_ptr(&self) -> *const u8 {
+		self.ptr
+	}
+}
+
+impl Drop for UnsizedAlloc {
+	fn drop(&mut self) {
+		// SAFETY: `ptr` was allocated in `new` with same layout
+		unsafe { dealloc(self.ptr, self.layout) };
+	}
+}
@@ -0,0 +1,133 @@
+use std::mem::{align_of, size_of};
+
+use layout_inspect::{
+	defs::{DefArray, DefRepr, DefStruct, DefStructField, DefType},
+	inspect, Inspect,
+};
+use memoffset::offset_of;
+
+#[test]
+fn array_primitive() {
+	let type_defs = inspect::<[u8; 32]>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Array(DefArray {
+			name: "[u8; 32]".to_string(),
+			size: 32,
+			align: 1,
+			value_type_id: 1,
+			len: 32,
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u8");
+}
+
+#[test]
+fn array_empty() {
+	let type_defs = inspect::<[u64; 0]>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Array(DefArray {
+			name: "[u64; 0]".to_string(),
+			size: 0,
+			align: align_of::<u64>(),
+			value_type_id: 1,
+			len: 0,
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u64");
+}
+
+#[test]
+fn array_nested() {
+	let type_defs = inspect::<[[f32; 4]; 2]>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Array(DefArray {
+			name: "[[f32; 4]; 2]".to_string(),
+			size: size_of::<f32>() * 8,
+			align: align_of::<f32>(),
+			value_type_id: 1,
+			len: 2,
+		})
+	);
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Array(DefArray {
+			name: "[f32; 4]".to_string(),
+			size: size_of::<f32>() * 4,
+			align: align_of::<f32>(),
+			value_type_id: 2,
+			len: 4,
+		})
+	);
+
+	assert_eq!(type_defs[2].name(), "f32");
+}
+
+#[test]
+fn array_in_struct() {
+	#[derive(Inspect)]
+	struct Foo {
+		hash: [u8; 32],
+		vector: [f32; 4],
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Struct(DefStruct {
+			name: "Foo".to_string(),
+			ser_name: "Foo".to_string(),
+			size: Some(size_of::<Foo>()),
+			align: Some(align_of::<Foo>()),
+			fields: vec![
+				DefStructField {
+					name: "hash".to_string(),
+					ser_name: "hash".to_string(),
+					type_id: 1,
+					offset: offset_of!(Foo, hash),
+					flatten: false,
+					skip: false,
+				},
+				DefStructField {
+					name: "vector".to_string(),
+					ser_name: "vector".to_string(),
+					type_id: 3,
+					offset: offset_of!(Foo, vector),
+					flatten: false,
+					skip: false,
+				},
+			],
+			tag: None,
+			transparent: false,
+			repr: DefRepr::default(),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "[u8; 32]");
+	assert_eq!(type_defs[2].name(), "u8");
+	assert_eq!(type_defs[3].name(), "[f32; 4]");
+	assert_eq!(type_defs[4].name(), "f32");
+}
+
+#[test]
+fn array_in_generic_struct() {
+	#[derive(Inspect)]
+	struct Foo<T> {
+		inner: T,
+	}
+
+	let type_defs = inspect::<Foo<[u16; 3]>>();
+
+	assert_eq!(type_defs[0].name(), "Foo<[u16; 3]>");
+	assert_eq!(type_defs[1].name(), "[u16; 3]");
+	assert_eq!(type_defs[2].name(), "u16");
+}
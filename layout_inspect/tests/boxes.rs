@@ -16,6 +16,8 @@ fn boxed_primitive() {
 			size: size_of::<usize>(),
 			align: align_of::<usize>(),
 			value_type_id: 1,
+			ptr_offset: 0,
+			metadata_offset: None,
 		})
 	);
 
@@ -39,6 +41,8 @@ fn boxed_struct() {
 			size: size_of::<usize>(),
 			align: align_of::<usize>(),
 			value_type_id: 1,
+			ptr_offset: 0,
+			metadata_offset: None,
 		})
 	);
 
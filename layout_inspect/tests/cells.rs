@@ -0,0 +1,296 @@
+use std::{
+	cell::{Cell, OnceCell, RefCell, UnsafeCell},
+	mem::{align_of, size_of},
+	sync::{
+		atomic::{AtomicBool, AtomicPtr, AtomicU32},
+		Mutex, OnceLock, RwLock,
+	},
+};
+
+use layout_inspect::{
+	defs::{DefAtomic, DefCell, DefNiche, DefNicheValue, DefOnceCell, DefType, DefUnsafeCell},
+	inspect,
+};
+
+/// Read value of type `V` at `offset` bytes from start of `value`
+fn read_at<T, V: Copy>(value: &T, offset: usize) -> V {
+	assert!(offset + size_of::<V>() <= size_of::<T>());
+	// SAFETY: Checked within bounds. Callers only read initialized fields.
+	unsafe { ((value as *const T as *const u8).add(offset) as *const V).read_unaligned() }
+}
+
+#[test]
+fn cell() {
+	let type_defs = inspect::<Cell<u32>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Cell(DefCell {
+			name: "Cell<u32>".to_string(),
+			size: 4,
+			align: 4,
+			value_type_id: 1,
+			value_offset: 0,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "u32");
+}
+
+#[test]
+fn unsafe_cell() {
+	let type_defs = inspect::<UnsafeCell<u16>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::UnsafeCell(DefUnsafeCell {
+			name: "UnsafeCell<u16>".to_string(),
+			size: 2,
+			align: 2,
+			value_type_id: 1,
+			value_offset: 0,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "u16");
+}
+
+#[test]
+fn ref_cell() {
+	let type_defs = inspect::<RefCell<u64>>();
+	let def = type_defs[0].to_ref_cell().unwrap();
+
+	assert_eq!(def.name, "RefCell<u64>");
+	assert_eq!(def.size, size_of::<RefCell<u64>>());
+	assert_eq!(def.align, align_of::<RefCell<u64>>());
+	assert_eq!(def.value_type_id, 1);
+	assert_eq!(def.borrow_size, size_of::<isize>());
+	assert_eq!(type_defs[1].name(), "u64");
+
+	let cell = RefCell::new(0x0123_4567_89ab_cdef_u64);
+	assert_eq!(
+		read_at::<_, u64>(&cell, def.value_offset),
+		0x0123_4567_89ab_cdef
+	);
+	assert_eq!(read_at::<_, isize>(&cell, def.borrow_offset), 0);
+	{
+		let _borrow = cell.borrow();
+		assert_eq!(read_at::<_, isize>(&cell, def.borrow_offset), 1);
+	}
+	{
+		let _borrow = cell.borrow_mut();
+		assert!(read_at::<_, isize>(&cell, def.borrow_offset) < 0);
+	}
+}
+
+#[test]
+fn ref_cell_small_value() {
+	let type_defs = inspect::<RefCell<u8>>();
+	let def = type_defs[0].to_ref_cell().unwrap();
+
+	let cell = RefCell::new(123u8);
+	let _borrows = (cell.borrow(), cell.borrow(), cell.borrow());
+	assert_eq!(read_at::<_, u8>(&cell, def.value_offset), 123);
+	assert_eq!(read_at::<_, isize>(&cell, def.borrow_offset), 3);
+}
+
+#[test]
+fn mutex() {
+	let type_defs = inspect::<Mutex<u32>>();
+	let def = type_defs[0].to_mutex().unwrap();
+
+	assert_eq!(def.name, "Mutex<u32>");
+	assert_eq!(def.size, size_of::<Mutex<u32>>());
+	assert_eq!(def.align, align_of::<Mutex<u32>>());
+	assert_eq!(def.value_type_id, 1);
+	assert_eq!(type_defs[1].name(), "u32");
+
+	let mutex = Mutex::new(0xdead_beef_u32);
+	assert_eq!(read_at::<_, u32>(&mutex, def.value_offset), 0xdead_beef);
+}
+
+#[test]
+fn rw_lock() {
+	let type_defs = inspect::<RwLock<u64>>();
+	let def = type_defs[0].to_rw_lock().unwrap();
+
+	assert_eq!(def.name, "RwLock<u64>");
+	assert_eq!(def.size, size_of::<RwLock<u64>>());
+	assert_eq!(def.value_type_id, 1);
+
+	let lock = RwLock::new(u64::MAX - 1);
+	assert_eq!(read_at::<_, u64>(&lock, def.value_offset), u64::MAX - 1);
+}
+
+#[test]
+fn once_cell_niche() {
+	let type_defs = inspect::<OnceCell<bool>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::OnceCell(DefOnceCell {
+			name: "OnceCell<bool>".to_string(),
+			size: 1,
+			align: 1,
+			value_type_id: 1,
+			value_offset: 0,
+			niche: Some(DefNiche {
+				offset: 0,
+				size: 1,
+				untagged_variant: "Some".to_string(),
+				values: vec![DefNicheValue {
+					variant: "None".to_string(),
+					value: 2,
+				}],
+			}),
+		})
+	);
+
+	let cell = OnceCell::<bool>::new();
+	assert_eq!(read_at::<_, u8>(&cell, 0), 2);
+	cell.set(true).unwrap();
+	assert_eq!(read_at::<_, u8>(&cell, 0), 1);
+}
+
+#[test]
+fn once_cell_tagged() {
+	let type_defs = inspect::<OnceCell<u32>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::OnceCell(DefOnceCell {
+			name: "OnceCell<u32>".to_string(),
+			size: 8,
+			align: 4,
+			value_type_id: 1,
+			value_offset: 4,
+			niche: None,
+		})
+	);
+
+	let cell = OnceCell::new();
+	cell.set(0x1234_5678_u32).unwrap();
+	assert_eq!(read_at::<_, u32>(&cell, 4), 0x1234_5678);
+}
+
+#[test]
+fn once_lock() {
+	let type_defs = inspect::<OnceLock<u16>>();
+	let def = type_defs[0].to_once_lock().unwrap();
+
+	assert_eq!(def.name, "OnceLock<u16>");
+	assert_eq!(def.size, size_of::<OnceLock<u16>>());
+	assert_eq!(def.value_type_id, 1);
+	assert_eq!(type_defs[1].name(), "u16");
+
+	let lock = OnceLock::new();
+	lock.set(0xabcd_u16).unwrap();
+	assert_eq!(read_at::<_, u16>(&lock, def.value_offset), 0xabcd);
+}
+
+// Larger than default stack of test threads (2 MiB)
+const HUGE: usize = 4 << 20;
+type Huge = [u8; HUGE];
+
+/// Run `f` on a thread with enough stack to create wrappers around `Huge`
+fn with_big_stack<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> R {
+	std::thread::Builder::new()
+		.stack_size(HUGE * 32)
+		.spawn(f)
+		.unwrap()
+		.join()
+		.unwrap()
+}
+
+#[test]
+fn huge_values() {
+	// Finding offsets must not create wrappers around values on this
+	// thread's stack, which would overflow it
+	let mutex_offset = inspect::<Mutex<Huge>>()[0].to_mutex().unwrap().value_offset;
+	let rw_lock_offset = inspect::<RwLock<Huge>>()[0]
+		.to_rw_lock()
+		.unwrap()
+		.value_offset;
+	let once_lock_offset = inspect::<OnceLock<Huge>>()[0]
+		.to_once_lock()
+		.unwrap()
+		.value_offset;
+	let type_defs = inspect::<RefCell<Huge>>();
+	let ref_cell_def = type_defs[0].to_ref_cell().unwrap();
+	let (ref_cell_offset, borrow_offset) = (ref_cell_def.value_offset, ref_cell_def.borrow_offset);
+
+	// Check against real values
+	let offset_of_value = |wrapper: *const u8, value: *const u8| value as usize - wrapper as usize;
+	let actual = with_big_stack(move || {
+		let mutex = Box::new(Mutex::new([1u8; HUGE]));
+		let guard = mutex.lock().unwrap();
+		offset_of_value(&*mutex as *const _ as *const u8, guard.as_ptr())
+	});
+	assert_eq!(mutex_offset, actual);
+
+	let actual = with_big_stack(move || {
+		let lock = Box::new(RwLock::new([1u8; HUGE]));
+		let guard = lock.read().unwrap();
+		offset_of_value(&*lock as *const _ as *const u8, guard.as_ptr())
+	});
+	assert_eq!(rw_lock_offset, actual);
+
+	let actual = with_big_stack(move || {
+		let lock = Box::new(OnceLock::new());
+		let value: &Huge = lock.get_or_init(|| [1u8; HUGE]);
+		offset_of_value(&*lock as *const _ as *const u8, value.as_ptr())
+	});
+	assert_eq!(once_lock_offset, actual);
+
+	let (actual, borrow) = with_big_stack(move || {
+		let cell = Box::new(RefCell::new([1u8; HUGE]));
+		let _borrows = (cell.borrow(), cell.borrow());
+		let value_offset = offset_of_value(&*cell as *const _ as *const u8, cell.as_ptr() as _);
+		(value_offset, read_at::<_, isize>(&*cell, borrow_offset))
+	});
+	assert_eq!(ref_cell_offset, actual);
+	assert_eq!(borrow, 2);
+}
+
+#[test]
+fn atomics() {
+	let type_defs = inspect::<(AtomicU32, AtomicBool)>();
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Atomic(DefAtomic {
+			name: "AtomicU32".to_string(),
+			size: 4,
+			align: 4,
+			value_type_id: 2,
+			value_offset: 0,
+		})
+	);
+	assert_eq!(type_defs[2].name(), "u32");
+	assert_eq!(
+		&type_defs[3],
+		&DefType::Atomic(DefAtomic {
+			name: "AtomicBool".to_string(),
+			size: 1,
+			align: 1,
+			value_type_id: 4,
+			value_offset: 0,
+		})
+	);
+	assert_eq!(type_defs[4].name(), "bool");
+}
+
+#[test]
+fn atomic_ptr() {
+	let type_defs = inspect::<AtomicPtr<u8>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Atomic(DefAtomic {
+			name: "AtomicPtr<u8>".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			value_offset: 0,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "*mut u8");
+}
@@ -0,0 +1,126 @@
+use std::{
+	collections::{
+		hash_map::DefaultHasher, BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque,
+	},
+	hash::BuildHasherDefault,
+	mem::{align_of, size_of},
+};
+
+use layout_inspect::{
+	defs::{DefMap, DefSeq, DefSet, DefType},
+	inspect, Inspect,
+};
+
+#[test]
+fn hash_map() {
+	let type_defs = inspect::<HashMap<u32, String>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Map(DefMap {
+			name: "HashMap<u32,String>".to_string(),
+			size: size_of::<HashMap<u32, String>>(),
+			align: align_of::<HashMap<u32, String>>(),
+			key_type_id: 1,
+			value_type_id: 2,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "u32");
+	assert_eq!(type_defs[2].name(), "String");
+}
+
+#[test]
+fn btree_map() {
+	let type_defs = inspect::<BTreeMap<u8, u8>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Map(DefMap {
+			name: "BTreeMap<u8,u8>".to_string(),
+			size: size_of::<BTreeMap<u8, u8>>(),
+			align: align_of::<BTreeMap<u8, u8>>(),
+			key_type_id: 1,
+			value_type_id: 1,
+		})
+	);
+}
+
+#[test]
+fn sets() {
+	let type_defs = inspect::<(HashSet<u16>, BTreeSet<u16>)>();
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Set(DefSet {
+			name: "HashSet<u16>".to_string(),
+			size: size_of::<HashSet<u16>>(),
+			align: align_of::<HashSet<u16>>(),
+			value_type_id: 2,
+		})
+	);
+	assert_eq!(
+		&type_defs[3],
+		&DefType::Set(DefSet {
+			name: "BTreeSet<u16>".to_string(),
+			size: size_of::<BTreeSet<u16>>(),
+			align: align_of::<BTreeSet<u16>>(),
+			value_type_id: 2,
+		})
+	);
+}
+
+#[test]
+fn seqs() {
+	let type_defs = inspect::<(VecDeque<u64>, BinaryHeap<u64>, LinkedList<u64>)>();
+
+	let seqs = [&type_defs[1], &type_defs[3], &type_defs[4]]
+		.map(|def| def.to_seq().unwrap())
+		.map(|def| (&def.name[..], def.size, def.value_type_id));
+	assert_eq!(
+		seqs,
+		[
+			("VecDeque<u64>", size_of::<VecDeque<u64>>(), 2),
+			("BinaryHeap<u64>", size_of::<BinaryHeap<u64>>(), 2),
+			("LinkedList<u64>", size_of::<LinkedList<u64>>(), 2),
+		]
+	);
+}
+
+#[test]
+fn struct_containing_map() {
+	#[derive(Inspect)]
+	#[allow(dead_code)]
+	struct Registry {
+		by_id: HashMap<u32, Vec<u8>>,
+		queue: VecDeque<u32>,
+	}
+
+	let type_defs = inspect::<Registry>();
+
+	assert_eq!(type_defs[0].name(), "Registry");
+	assert!(type_defs[1].to_map().is_some());
+	assert_eq!(
+		type_defs[0].to_struct().unwrap().size,
+		Some(size_of::<Registry>())
+	);
+	let seq: &DefSeq = type_defs.iter().find_map(|def| def.to_seq()).unwrap();
+	assert_eq!(seq.name, "VecDeque<u32>");
+}
+
+#[test]
+fn custom_hasher() {
+	type Hasher = BuildHasherDefault<DefaultHasher>;
+	let type_defs = inspect::<(
+		HashMap<u32, u8, Hasher>,
+		HashSet<u32, Hasher>,
+		HashMap<u32, u8>,
+	)>();
+
+	let hasher = std::any::type_name::<Hasher>();
+	let map = type_defs[1].to_map().unwrap();
+	assert_eq!(map.name, format!("HashMap<u32,u8,{hasher}>"));
+	assert_eq!(map.size, size_of::<HashMap<u32, u8, Hasher>>());
+	let set = type_defs[4].to_set().unwrap();
+	assert_eq!(set.name, format!("HashSet<u32,{hasher}>"));
+	assert_eq!(type_defs[5].name(), "HashMap<u32,u8>");
+}
@@ -0,0 +1,136 @@
+use std::{
+	fmt::Debug,
+	mem::{align_of, size_of, transmute_copy},
+	sync::Arc,
+};
+
+use layout_inspect::{
+	defs::{DefBox, DefRef, DefType},
+	inspect, inspect_dyn, Inspect,
+};
+
+trait Plugin: Debug {}
+
+#[derive(Inspect, Debug)]
+struct Foo {
+	x: u32,
+}
+
+impl Plugin for Foo {}
+
+#[derive(Inspect, Debug)]
+struct Bar {
+	y: u8,
+}
+
+impl Plugin for Bar {}
+
+inspect_dyn!(dyn Plugin => Foo, Bar);
+
+trait Shape {}
+
+inspect_dyn!(dyn Shape + Send);
+
+#[derive(Inspect)]
+#[allow(dead_code)]
+struct Host {
+	plugins: Vec<Box<dyn Plugin>>,
+	shape: Arc<dyn Shape + Send>,
+}
+
+#[test]
+fn box_dyn() {
+	let type_defs = inspect::<Box<dyn Plugin>>();
+
+	// Find pointer offsets in a real `Box<dyn Plugin>`
+	let boxed: Box<dyn Plugin> = Box::new(Foo { x: 1 });
+	let data_ptr = &*boxed as *const dyn Plugin as *const u8 as usize;
+	// SAFETY: Trait object pointers are 2 `usize`s
+	let words: [usize; 2] = unsafe { transmute_copy(&boxed) };
+	let (ptr_offset, vtable_offset) = if words[0] == data_ptr {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	};
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Box(DefBox {
+			name: "Box<dyn Plugin>".to_string(),
+			size: size_of::<usize>() * 2,
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			ptr_offset,
+			metadata_offset: Some(vtable_offset),
+		})
+	);
+
+	let def = type_defs[1].to_dyn().unwrap();
+	assert_eq!(def.name, "dyn Plugin");
+	assert_eq!(def.size, None);
+	assert_eq!(def.align, None);
+	assert_eq!(def.implementor_type_ids, Some(vec![2, 4]));
+	assert_eq!(type_defs[2].name(), "Foo");
+	assert_eq!(type_defs[4].name(), "Bar");
+
+	assert_eq!(def.ptr_offset, ptr_offset);
+	assert_eq!(def.vtable_offset, vtable_offset);
+}
+
+#[test]
+fn ref_dyn() {
+	let type_defs = inspect::<&mut dyn Plugin>();
+
+	// Find pointer offsets in a real `&mut dyn Plugin`
+	let mut plugin = Foo { x: 1 };
+	let data_ptr = &plugin as *const Foo as usize;
+	let obj: &mut dyn Plugin = &mut plugin;
+	// SAFETY: Trait object pointers are 2 `usize`s
+	let words: [usize; 2] = unsafe { transmute_copy(&obj) };
+	let (ptr_offset, vtable_offset) = if words[0] == data_ptr {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	};
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ref(DefRef {
+			name: "&mut dyn Plugin".to_string(),
+			size: size_of::<usize>() * 2,
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			mutable: true,
+			fat: true,
+			ptr_offset,
+			metadata_offset: Some(vtable_offset),
+		})
+	);
+	assert_eq!(type_defs[1].name(), "dyn Plugin");
+}
+
+#[test]
+fn dyn_without_implementors() {
+	let type_defs = inspect::<Arc<dyn Shape + Send>>();
+
+	let arc = type_defs[0].to_arc().unwrap();
+	assert_eq!(arc.name, "Arc<dyn Shape + Send>");
+	assert_eq!(arc.value_offset, None);
+
+	let def = type_defs[1].to_dyn().unwrap();
+	assert_eq!(def.name, "dyn Shape + Send");
+	assert_eq!(def.implementor_type_ids, None);
+	assert_eq!(type_defs[1].size(), None);
+	assert_eq!(type_defs[1].align(), None);
+}
+
+#[test]
+fn struct_with_dyn_fields() {
+	let type_defs = inspect::<Host>();
+
+	let names = type_defs.iter().map(|def| def.name()).collect::<Vec<_>>();
+	assert!(names.contains(&"Vec<Box<dyn Plugin>>"));
+	assert!(names.contains(&"dyn Plugin"));
+	assert!(names.contains(&"Arc<dyn Shape + Send>"));
+	assert!(names.contains(&"dyn Shape + Send"));
+}
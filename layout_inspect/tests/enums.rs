@@ -1,10 +1,43 @@
+#![cfg_attr(
+	feature = "nightly_offset_of_enum",
+	feature(offset_of_enum, offset_of_nested)
+)]
+#![cfg_attr(feature = "nightly", allow(incomplete_features), feature(repr128))]
+
 use std::mem::{align_of, size_of, transmute};
 
 use layout_inspect::{
-	defs::{DefEnum, DefEnumTag, DefEnumVariant, DefType},
+	defs::{
+		DefEnum, DefEnumTag, DefEnumTagLayout, DefEnumVariant, DefEnumVariantField,
+		DefEnumVariantPayload, DefNiche, DefNicheValue, DefRepr, DefType,
+	},
 	inspect, Inspect,
 };
 
+// Offset of a field in an enum variant, read from address of field in `value`
+macro_rules! variant_field_offset {
+	($ty:ty, $value:expr, $variant:ident.$field:tt) => {{
+		type Enum = $ty;
+		let value: Enum = $value;
+		let base = &value as *const Enum as usize;
+		#[allow(unreachable_patterns)]
+		match &value {
+			Enum::$variant { $field: field, .. } => Some(field as *const _ as usize - base),
+			_ => unreachable!(),
+		}
+	}};
+}
+
+// Payload starts at lowest offset of variant's fields
+macro_rules! variant_payload_offset {
+	($ty:ty, $value:expr, $($variant:ident.$field:tt),+) => {
+		[$(variant_field_offset!($ty, $value, $variant.$field)),+]
+			.into_iter()
+			.min()
+			.flatten()
+	};
+}
+
 #[test]
 fn enum_fieldless() {
 	#[allow(dead_code)]
@@ -26,16 +59,21 @@ fn enum_fieldless() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: Some("Opt1".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: Some("Opt2".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 
@@ -68,16 +106,21 @@ fn enum_fieldless_raw_identifier_variant_name() {
 					name: "type".to_string(),
 					discriminant: 0,
 					ser_value: Some("type".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "enum".to_string(),
 					discriminant: 1,
 					ser_value: Some("enum".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 }
@@ -104,16 +147,21 @@ fn enum_fieldless_with_serde_type_rename() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: Some("Opt1".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: Some("Opt2".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 }
@@ -141,16 +189,21 @@ fn enum_fieldless_with_serde_variant_rename() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: Some("o1".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: Some("o2".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 }
@@ -179,22 +232,28 @@ fn enum_fieldless_with_serde_variants_rename_all() {
 					name: "OptOne".to_string(),
 					discriminant: 0,
 					ser_value: Some("optOne".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "OptTwo".to_string(),
 					discriminant: 1,
 					ser_value: Some("optTwo".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "OptThree".to_string(),
 					discriminant: 2,
 					ser_value: Some("opt_three".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 }
@@ -222,28 +281,35 @@ fn enum_fieldless_with_discriminants() {
 					name: "Opt1".to_string(),
 					discriminant: 5,
 					ser_value: Some("Opt1".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 10,
 					ser_value: Some("Opt2".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "Opt3".to_string(),
 					discriminant: 11,
 					ser_value: Some("Opt3".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "Opt4".to_string(),
 					discriminant: 12,
 					ser_value: Some("Opt4".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 
@@ -280,16 +346,35 @@ fn enum_fieldful() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: None,
-					value_type_id: Some(1)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 1,
+						offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: None,
-					value_type_id: Some(2)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 2,
+						offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 
@@ -320,16 +405,35 @@ fn enum_fieldful_with_serde_tag() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: None,
-					value_type_id: Some(1)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 1,
+						offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: None,
-					value_type_id: Some(2)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 2,
+						offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
 				},
 			],
 			tag: DefEnumTag::Tag("type".to_string()),
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 }
@@ -356,19 +460,38 @@ fn enum_fieldful_with_serde_tag_and_content() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: None,
-					value_type_id: Some(1)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 1,
+						offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: None,
-					value_type_id: Some(2)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 2,
+						offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
 				},
 			],
 			tag: DefEnumTag::TagAndContent {
 				tag: "t".to_string(),
 				content: "c".to_string()
 			},
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 }
@@ -395,16 +518,35 @@ fn enum_fieldful_with_serde_untagged() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: None,
-					value_type_id: Some(1)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 1,
+						offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt1(0), Opt1.0),
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: None,
-					value_type_id: Some(2)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 2,
+						offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
 				},
 			],
 			tag: DefEnumTag::Untagged,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 }
@@ -432,16 +574,28 @@ fn enum_mixed_fieldless_and_fieldful() {
 					name: "Opt1".to_string(),
 					discriminant: 0,
 					ser_value: Some("Opt1".to_string()),
-					value_type_id: None
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
 				},
 				DefEnumVariant {
 					name: "Opt2".to_string(),
 					discriminant: 1,
 					ser_value: None,
-					value_type_id: Some(1)
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 1,
+						offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0),
 				},
 			],
 			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: Some(DefEnumTagLayout { offset: 0, size: 1 }),
+			niche: None,
 		})
 	);
 
@@ -456,12 +610,1121 @@ fn enum_mixed_fieldless_and_fieldful() {
 	assert_eq!(to_bytes(Foo::Opt2(10)), [1, 10]);
 }
 
+#[test]
+fn enum_multi_field_tuple_variants() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1(u8, u32),
+		Opt2(),
+		Opt3(u16, u8, String),
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	// `Opt1` and `Opt2` are stored in a niche in `String`.
+	// Location of niche depends on version of std, so checked below.
+	let niche = type_defs[0].to_enum().unwrap().niche.as_ref();
+	let niche_layout = niche.map(|niche| (niche.offset, niche.values[0].value));
+	assert!(niche_layout.is_some());
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Enum(DefEnum {
+			name: "Foo".to_string(),
+			ser_name: "Foo".to_string(),
+			size: size_of::<Foo>(),
+			align: align_of::<Foo>(),
+			variants: vec![
+				DefEnumVariant {
+					name: "Opt1".to_string(),
+					discriminant: 0,
+					ser_value: None,
+					payload: DefEnumVariantPayload::Tuple(vec![
+						DefEnumVariantField {
+							name: "0".to_string(),
+							ser_name: "0".to_string(),
+							type_id: 1,
+							offset: variant_field_offset!(Foo, Foo::Opt1(0, 0), Opt1.0),
+							flatten: false,
+							skip: false,
+						},
+						DefEnumVariantField {
+							name: "1".to_string(),
+							ser_name: "1".to_string(),
+							type_id: 2,
+							offset: variant_field_offset!(Foo, Foo::Opt1(0, 0), Opt1.1),
+							flatten: false,
+							skip: false,
+						},
+					]),
+					payload_offset: variant_payload_offset!(Foo, Foo::Opt1(0, 0), Opt1.0, Opt1.1),
+				},
+				DefEnumVariant {
+					name: "Opt2".to_string(),
+					discriminant: 1,
+					ser_value: None,
+					payload: DefEnumVariantPayload::Tuple(vec![]),
+					payload_offset: None,
+				},
+				DefEnumVariant {
+					name: "Opt3".to_string(),
+					discriminant: 2,
+					ser_value: None,
+					payload: DefEnumVariantPayload::Tuple(vec![
+						DefEnumVariantField {
+							name: "0".to_string(),
+							ser_name: "0".to_string(),
+							type_id: 3,
+							offset: variant_field_offset!(Foo, Foo::Opt3(0, 0, String::new()), Opt3.0),
+							flatten: false,
+							skip: false,
+						},
+						DefEnumVariantField {
+							name: "1".to_string(),
+							ser_name: "1".to_string(),
+							type_id: 1,
+							offset: variant_field_offset!(Foo, Foo::Opt3(0, 0, String::new()), Opt3.1),
+							flatten: false,
+							skip: false,
+						},
+						DefEnumVariantField {
+							name: "2".to_string(),
+							ser_name: "2".to_string(),
+							type_id: 4,
+							offset: variant_field_offset!(Foo, Foo::Opt3(0, 0, String::new()), Opt3.2),
+							flatten: false,
+							skip: false,
+						},
+					]),
+					payload_offset: variant_payload_offset!(
+						Foo,
+						Foo::Opt3(0, 0, String::new()),
+						Opt3.0,
+						Opt3.1,
+						Opt3.2
+					),
+				},
+			],
+			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: None,
+			niche: niche_layout.map(|(offset, value)| {
+				DefNiche {
+					offset,
+					size: size_of::<usize>(),
+					untagged_variant: "Opt3".to_string(),
+					values: vec![
+						DefNicheValue {
+							variant: "Opt1".to_string(),
+							value,
+						},
+						DefNicheValue {
+							variant: "Opt2".to_string(),
+							value: value + 1,
+						},
+					],
+				}
+			}),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u8");
+	assert_eq!(type_defs[2].name(), "u32");
+	assert_eq!(type_defs[3].name(), "u16");
+	assert_eq!(type_defs[4].name(), "String");
+
+	if let Some((_, value)) = niche_layout {
+		let def = type_defs[0].to_enum().unwrap();
+		assert_eq!(read_niche(&Foo::Opt1(1, 2), def), value);
+		assert_eq!(read_niche(&Foo::Opt2(), def), value + 1);
+	}
+}
+
+#[test]
+fn enum_multi_field_tuple_variant_offsets() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1(u8, u32, u16),
+		Opt2,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let fields = type_defs[0].to_enum().unwrap().variants[0].payload.fields();
+
+	// Read fields from raw memory using the offsets
+	let value = Foo::Opt1(1, 2, 3);
+	let ptr = &value as *const Foo as *const u8;
+	unsafe {
+		assert_eq!(*ptr.add(fields[0].offset.unwrap()), 1);
+		assert_eq!(
+			(ptr.add(fields[1].offset.unwrap()) as *const u32).read_unaligned(),
+			2
+		);
+		assert_eq!(
+			(ptr.add(fields[2].offset.unwrap()) as *const u16).read_unaligned(),
+			3
+		);
+	}
+}
+
+#[test]
+fn enum_struct_variants() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1 { small: u8, big: u32 },
+		Opt2 { r#type: String },
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	// `Opt1` is stored in a niche in `String`.
+	// Location of niche depends on version of std, so checked below.
+	let niche = type_defs[0].to_enum().unwrap().niche.as_ref().unwrap();
+	let (niche_offset, niche_value) = (niche.offset, niche.values[0].value);
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Enum(DefEnum {
+			name: "Foo".to_string(),
+			ser_name: "Foo".to_string(),
+			size: size_of::<Foo>(),
+			align: align_of::<Foo>(),
+			variants: vec![
+				DefEnumVariant {
+					name: "Opt1".to_string(),
+					discriminant: 0,
+					ser_value: None,
+					payload: DefEnumVariantPayload::Struct(vec![
+						DefEnumVariantField {
+							name: "small".to_string(),
+							ser_name: "small".to_string(),
+							type_id: 1,
+							offset: variant_field_offset!(Foo, Foo::Opt1 { small: 0, big: 0 }, Opt1.small),
+							flatten: false,
+							skip: false,
+						},
+						DefEnumVariantField {
+							name: "big".to_string(),
+							ser_name: "big".to_string(),
+							type_id: 2,
+							offset: variant_field_offset!(Foo, Foo::Opt1 { small: 0, big: 0 }, Opt1.big),
+							flatten: false,
+							skip: false,
+						},
+					]),
+					payload_offset: variant_payload_offset!(
+						Foo,
+						Foo::Opt1 { small: 0, big: 0 },
+						Opt1.small,
+						Opt1.big
+					),
+				},
+				DefEnumVariant {
+					name: "Opt2".to_string(),
+					discriminant: 1,
+					ser_value: None,
+					payload: DefEnumVariantPayload::Struct(vec![DefEnumVariantField {
+						name: "type".to_string(),
+						ser_name: "type".to_string(),
+						type_id: 3,
+						offset: variant_field_offset!(
+							Foo,
+							Foo::Opt2 {
+								r#type: String::new()
+							},
+							Opt2.r#type
+						),
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: variant_field_offset!(
+						Foo,
+						Foo::Opt2 {
+							r#type: String::new()
+						},
+						Opt2.r#type
+					),
+				},
+			],
+			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout: None,
+			niche: Some(DefNiche {
+				offset: niche_offset,
+				size: size_of::<usize>(),
+				untagged_variant: "Opt2".to_string(),
+				values: vec![DefNicheValue {
+					variant: "Opt1".to_string(),
+					value: niche_value,
+				}],
+			}),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u8");
+	assert_eq!(type_defs[2].name(), "u32");
+	assert_eq!(type_defs[3].name(), "String");
+
+	let string_def = type_defs[3].to_string().unwrap();
+	assert!(niche_offset == string_def.ptr_offset || niche_offset == string_def.cap_offset);
+	let value = Foo::Opt1 { small: 1, big: 2 };
+	assert_eq!(
+		read_niche(&value, type_defs[0].to_enum().unwrap()),
+		niche_value
+	);
+}
+
+#[test]
+fn enum_struct_variant_with_serde_attrs() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[serde(rename_all = "snake_case")]
+	enum Foo {
+		#[serde(rename_all = "camelCase")]
+		OptOne {
+			field_one: u8,
+			#[serde(rename = "two")]
+			field_two: u16,
+			#[serde(skip)]
+			field_three: u32,
+			#[serde(flatten)]
+			field_four: Bar,
+		},
+		OptTwo,
+	}
+
+	#[derive(Default, Inspect)]
+	struct Bar {
+		x: u8,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let variants = &type_defs[0].to_enum().unwrap().variants;
+
+	assert_eq!(
+		variants[0].payload,
+		DefEnumVariantPayload::Struct(vec![
+			DefEnumVariantField {
+				name: "field_one".to_string(),
+				ser_name: "fieldOne".to_string(),
+				type_id: 1,
+				offset: variant_field_offset!(
+					Foo,
+					Foo::OptOne {
+						field_one: 0,
+						field_two: 0,
+						field_three: 0,
+						field_four: Bar { x: 0 },
+					},
+					OptOne.field_one
+				),
+				flatten: false,
+				skip: false,
+			},
+			DefEnumVariantField {
+				name: "field_two".to_string(),
+				ser_name: "two".to_string(),
+				type_id: 2,
+				offset: variant_field_offset!(
+					Foo,
+					Foo::OptOne {
+						field_one: 0,
+						field_two: 0,
+						field_three: 0,
+						field_four: Bar { x: 0 },
+					},
+					OptOne.field_two
+				),
+				flatten: false,
+				skip: false,
+			},
+			DefEnumVariantField {
+				name: "field_three".to_string(),
+				ser_name: "fieldThree".to_string(),
+				type_id: 3,
+				offset: variant_field_offset!(
+					Foo,
+					Foo::OptOne {
+						field_one: 0,
+						field_two: 0,
+						field_three: 0,
+						field_four: Bar { x: 0 },
+					},
+					OptOne.field_three
+				),
+				flatten: false,
+				skip: true,
+			},
+			DefEnumVariantField {
+				name: "field_four".to_string(),
+				ser_name: "fieldFour".to_string(),
+				type_id: 4,
+				offset: variant_field_offset!(
+					Foo,
+					Foo::OptOne {
+						field_one: 0,
+						field_two: 0,
+						field_three: 0,
+						field_four: Bar { x: 0 },
+					},
+					OptOne.field_four
+				),
+				flatten: true,
+				skip: false,
+			},
+		])
+	);
+
+	assert_eq!(variants[1].ser_value, Some("opt_two".to_string()));
+	assert_eq!(variants[1].payload, DefEnumVariantPayload::Unit);
+	assert_eq!(type_defs[4].name(), "Bar");
+}
+
+#[test]
+fn enum_struct_variant_offsets() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1 { small: u8, big: u64 },
+		Opt2 { other: u16 },
+	}
+
+	let type_defs = inspect::<Foo>();
+	let variants = &type_defs[0].to_enum().unwrap().variants;
+
+	// Read fields from raw memory using the offsets
+	let value = Foo::Opt1 {
+		small: 12,
+		big: 0x1234_5678_9abc_def0,
+	};
+	let ptr = &value as *const Foo as *const u8;
+	let small_offset = variants[0].payload.fields()[0].offset.unwrap();
+	let big_offset = variants[0].payload.fields()[1].offset.unwrap();
+	unsafe {
+		assert_eq!(*ptr.add(small_offset), 12);
+		assert_eq!(
+			(ptr.add(big_offset) as *const u64).read_unaligned(),
+			0x1234_5678_9abc_def0
+		);
+	}
+}
+
+#[test]
+fn enum_variant_offsets_without_default() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1(u8, Bar),
+		Opt2(u16),
+	}
+
+	#[derive(Inspect)]
+	struct Bar {
+		x: u32,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let variants = &type_defs[0].to_enum().unwrap().variants;
+
+	// Without `nightly_offset_of_enum` feature, variant can only be constructed to
+	// find offsets if all its fields implement `Default`
+	let bar = || Bar { x: 0 };
+	if cfg!(feature = "nightly_offset_of_enum") {
+		assert_eq!(
+			variants[0].payload.fields()[1].offset,
+			variant_field_offset!(Foo, Foo::Opt1(0, bar()), Opt1.1)
+		);
+	} else {
+		assert_eq!(variants[0].payload.fields()[0].offset, None);
+		assert_eq!(variants[0].payload.fields()[1].offset, None);
+		assert_eq!(variants[0].payload_offset, None);
+	}
+	assert_eq!(
+		variants[1].payload.fields()[0].offset,
+		variant_field_offset!(Foo, Foo::Opt2(0), Opt2.0)
+	);
+}
+
+#[test]
+fn enum_generic_one_type_param() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Maybe<T> {
+		Just(T),
+		Nothing,
+	}
+
+	#[derive(Inspect)]
+	struct Foo {
+		num: Maybe<u8>,
+		text: Maybe<String>,
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	// Without `nightly_offset_of_enum` feature, offsets are found by constructing
+	// variant with `Default` values, which is not possible for fields of type `T`
+	let (just_offset, tag_layout) = if cfg!(feature = "nightly_offset_of_enum") {
+		(
+			variant_field_offset!(Maybe<u8>, Maybe::Just(0), Just.0),
+			Some(DefEnumTagLayout { offset: 0, size: 1 }),
+		)
+	} else {
+		(None, None)
+	};
+
+	let foo_def = type_defs[0].to_struct().unwrap();
+	let maybe_u8_def = &type_defs[foo_def.fields[0].type_id as usize];
+	assert_eq!(
+		maybe_u8_def,
+		&DefType::Enum(DefEnum {
+			name: "Maybe<u8>".to_string(),
+			ser_name: "Maybe<u8>".to_string(),
+			size: size_of::<Maybe<u8>>(),
+			align: align_of::<Maybe<u8>>(),
+			variants: vec![
+				DefEnumVariant {
+					name: "Just".to_string(),
+					discriminant: 0,
+					ser_value: None,
+					payload: DefEnumVariantPayload::Tuple(vec![DefEnumVariantField {
+						name: "0".to_string(),
+						ser_name: "0".to_string(),
+						type_id: 2,
+						offset: just_offset,
+						flatten: false,
+						skip: false,
+					}]),
+					payload_offset: just_offset,
+				},
+				DefEnumVariant {
+					name: "Nothing".to_string(),
+					discriminant: 1,
+					ser_value: Some("Nothing".to_string()),
+					payload: DefEnumVariantPayload::Unit,
+					payload_offset: None,
+				},
+			],
+			tag: DefEnumTag::None,
+			repr: DefRepr::default(),
+			tag_layout,
+			niche: None,
+		})
+	);
+	assert_eq!(type_defs[2].name(), "u8");
+
+	let maybe_string_def = &type_defs[foo_def.fields[1].type_id as usize];
+	assert_eq!(maybe_string_def.name(), "Maybe<String>");
+	assert_eq!(maybe_string_def.size(), Some(size_of::<Maybe<String>>()));
+	let variant_ids = get_variant_ids(maybe_string_def);
+	assert_eq!(type_defs[variant_ids[0].unwrap()].name(), "String");
+
+	// Different instantiations are different types
+	assert_eq!(type_defs.len(), 5);
+}
+
+#[test]
+fn enum_generic_two_type_params() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[serde(rename = "Either")]
+	enum Or<L, R> {
+		Left(L),
+		Right { value: R, count: u8 },
+	}
+
+	let type_defs = inspect::<Or<u32, Or<u8, u16>>>();
+
+	let outer_def = type_defs[0].to_enum().unwrap();
+	assert_eq!(outer_def.name, "Or<u32,Or<u8,u16>>");
+	assert_eq!(outer_def.ser_name, "Either");
+	assert_eq!(outer_def.size, size_of::<Or<u32, Or<u8, u16>>>());
+
+	let variant_ids = get_variant_ids(&type_defs[0]);
+	assert_eq!(type_defs[variant_ids[0].unwrap()].name(), "u32");
+
+	let right_fields = outer_def.variants[1].payload.fields();
+	assert_eq!(right_fields[0].name, "value");
+	let inner_def = &type_defs[right_fields[0].type_id as usize];
+	assert_eq!(inner_def.name(), "Or<u8,u16>");
+	assert_eq!(type_defs[right_fields[1].type_id as usize].name(), "u8");
+
+	let inner_variant_ids = get_variant_ids(inner_def);
+	assert_eq!(type_defs[inner_variant_ids[0].unwrap()].name(), "u8");
+	let inner_right_fields = inner_def.to_enum().unwrap().variants[1].payload.fields();
+	assert_eq!(
+		type_defs[inner_right_fields[0].type_id as usize].name(),
+		"u16"
+	);
+}
+
+#[test]
+fn enum_repr_int() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(u16)]
+	enum Foo {
+		Opt1,
+		Opt2,
+	}
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(i32)]
+	enum Bar {
+		Opt1(u8),
+		Opt2,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(def.size, 2);
+	assert_eq!(
+		def.repr,
+		DefRepr {
+			int: Some("u16".to_string()),
+			..DefRepr::default()
+		}
+	);
+
+	let type_defs = inspect::<Bar>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(def.size, 8);
+	assert_eq!(
+		def.repr,
+		DefRepr {
+			int: Some("i32".to_string()),
+			..DefRepr::default()
+		}
+	);
+}
+
+#[test]
+fn enum_repr_c() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(C)]
+	enum Foo {
+		Opt1,
+		Opt2,
+	}
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(C, u8)]
+	enum Bar {
+		Opt1(u32),
+		Opt2,
+	}
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(
+		type_defs[0].to_enum().unwrap().repr,
+		DefRepr {
+			c: true,
+			..DefRepr::default()
+		}
+	);
+
+	let type_defs = inspect::<Bar>();
+	assert_eq!(
+		type_defs[0].to_enum().unwrap().repr,
+		DefRepr {
+			c: true,
+			int: Some("u8".to_string()),
+			..DefRepr::default()
+		}
+	);
+}
+
+#[test]
+fn enum_repr_int_layout() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(u8)]
+	enum Foo {
+		Opt1(u8, u32),
+		Opt2 { small: u16, big: u64 },
+		Opt3,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(
+		def.tag_layout,
+		Some(DefEnumTagLayout { offset: 0, size: 1 })
+	);
+
+	// Each variant is a `#[repr(C)]` struct with tag as first field
+	let variants = &def.variants;
+	let offsets = |index: usize| -> Vec<Option<usize>> {
+		variants[index]
+			.payload
+			.fields()
+			.iter()
+			.map(|field| field.offset)
+			.collect()
+	};
+	assert_eq!(offsets(0), vec![Some(1), Some(4)]);
+	assert_eq!(variants[0].payload_offset, Some(1));
+	assert_eq!(offsets(1), vec![Some(2), Some(8)]);
+	assert_eq!(variants[1].payload_offset, Some(2));
+	assert_eq!(variants[2].payload_offset, None);
+
+	// Read tag and fields from raw memory using the offsets
+	let value = Foo::Opt2 {
+		small: 0x1234,
+		big: 0x1234_5678_9abc_def0,
+	};
+	let ptr = &value as *const Foo as *const u8;
+	unsafe {
+		assert_eq!(*ptr, 1);
+		assert_eq!((ptr.add(2) as *const u16).read_unaligned(), 0x1234);
+		assert_eq!(
+			(ptr.add(8) as *const u64).read_unaligned(),
+			0x1234_5678_9abc_def0
+		);
+	}
+}
+
+#[test]
+fn enum_repr_c_layout() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(C)]
+	enum Foo {
+		Opt1(u8),
+		Opt2(u16, u64),
+	}
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(C, u8)]
+	enum Bar {
+		Opt1(u8),
+		Opt2(u32),
+	}
+
+	// `#[repr(C)]`: Tag is a C int, followed by union of variants' fields
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(
+		def.tag_layout,
+		Some(DefEnumTagLayout {
+			offset: 0,
+			size: size_of::<std::ffi::c_int>(),
+		})
+	);
+	assert_eq!(def.variants[0].payload.fields()[0].offset, Some(8));
+	assert_eq!(def.variants[0].payload_offset, Some(8));
+	assert_eq!(def.variants[1].payload.fields()[0].offset, Some(8));
+	assert_eq!(def.variants[1].payload.fields()[1].offset, Some(16));
+
+	let value = Foo::Opt2(0x1234, 0x1234_5678_9abc_def0);
+	let ptr = &value as *const Foo as *const u8;
+	unsafe {
+		assert_eq!((ptr as *const std::ffi::c_int).read(), 1);
+		assert_eq!((ptr.add(8) as *const u16).read(), 0x1234);
+		assert_eq!((ptr.add(16) as *const u64).read(), 0x1234_5678_9abc_def0);
+	}
+
+	// `#[repr(C, u8)]`: Tag is `u8`, followed by union of variants' fields
+	let type_defs = inspect::<Bar>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(
+		def.tag_layout,
+		Some(DefEnumTagLayout { offset: 0, size: 1 })
+	);
+	assert_eq!(def.variants[0].payload.fields()[0].offset, Some(4));
+	assert_eq!(def.variants[1].payload.fields()[0].offset, Some(4));
+
+	let value = Bar::Opt1(123);
+	let ptr = &value as *const Bar as *const u8;
+	unsafe {
+		assert_eq!(*ptr, 0);
+		assert_eq!(*ptr.add(4), 123);
+	}
+}
+
+#[test]
+fn enum_single_variant_has_no_tag() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1,
+	}
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(type_defs[0].to_enum().unwrap().tag_layout, None);
+}
+
+// References don't implement `Default`, so without `nightly_offset_of_enum`
+// feature, offsets of their fields in enum variants are unknown
+#[cfg(feature = "nightly_offset_of_enum")]
+#[test]
+fn enum_niche_has_no_tag() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo<'a> {
+		Opt1(&'a u32),
+		Opt2,
+	}
+
+	// Discriminant is stored in niche of reference (null), so no tag
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(def.size, size_of::<usize>());
+	assert_eq!(def.tag_layout, None);
+	assert_eq!(def.variants[0].payload_offset, Some(0));
+}
+
+#[test]
+fn enum_tag_layout() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1(u32),
+		Opt2(u16),
+	}
+
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	let tag_layout = def.tag_layout.as_ref().unwrap();
+	assert_eq!(tag_layout.offset, 0);
+
+	// Tag is widened to fill space before payloads
+	let payload_offset = def
+		.variants
+		.iter()
+		.map(|variant| variant.payload_offset.unwrap())
+		.min()
+		.unwrap();
+	assert!(tag_layout.size <= payload_offset);
+
+	// Read tag from raw memory
+	let read_tag = |value: &Foo| {
+		let ptr = value as *const Foo as *const u8;
+		unsafe { *ptr.add(tag_layout.offset) }
+	};
+	let tag1 = read_tag(&Foo::Opt1(u32::MAX));
+	let tag2 = read_tag(&Foo::Opt2(u16::MAX));
+	assert_ne!(tag1, tag2);
+}
+
+#[test]
+fn enum_fieldless_aligned_tag_layout() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(align(8))]
+	enum Foo {
+		Opt1,
+		Opt2,
+		Opt3,
+	}
+
+	// Rest of enum is padding, not tag
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(def.size, 8);
+	assert_eq!(
+		def.tag_layout,
+		Some(DefEnumTagLayout { offset: 0, size: 1 })
+	);
+}
+
+#[test]
+fn enum_niche() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1(bool),
+		Opt2,
+		Opt3,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	assert_eq!(def.size, 1);
+	assert_eq!(def.tag_layout, None);
+	assert_eq!(
+		def.niche,
+		Some(DefNiche {
+			offset: 0,
+			size: 1,
+			untagged_variant: "Opt1".to_string(),
+			values: vec![
+				DefNicheValue {
+					variant: "Opt2".to_string(),
+					value: 2,
+				},
+				DefNicheValue {
+					variant: "Opt3".to_string(),
+					value: 3,
+				},
+			],
+		})
+	);
+
+	assert_eq!(read_niche(&Foo::Opt2, def), 2);
+	assert_eq!(read_niche(&Foo::Opt3, def), 3);
+}
+
+#[test]
+fn enum_niche_untagged_variant_in_middle() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1,
+		Opt2(char),
+		Opt3,
+	}
+
+	// Value is reserved for untagged variant too
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	let niche = def.niche.as_ref().unwrap();
+	assert_eq!(niche.untagged_variant, "Opt2");
+	assert_eq!(
+		niche.values,
+		vec![
+			DefNicheValue {
+				variant: "Opt1".to_string(),
+				value: 0x110000,
+			},
+			DefNicheValue {
+				variant: "Opt3".to_string(),
+				value: 0x110002,
+			},
+		]
+	);
+
+	assert_eq!(read_niche(&Foo::Opt1, def), 0x110000);
+	assert_eq!(read_niche(&Foo::Opt3, def), 0x110002);
+}
+
+#[test]
+fn enum_niche_in_struct_field() {
+	#[derive(Inspect)]
+	struct Bar {
+		num: u64,
+		flag: bool,
+	}
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo<'a> {
+		Opt1(Bar),
+		Opt2,
+		Opt3(&'a u8),
+	}
+
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_enum().unwrap();
+	let niche = def.niche.as_ref().unwrap();
+	assert_eq!(niche.untagged_variant, "Opt1");
+	assert_eq!(niche.offset, memoffset::offset_of!(Bar, flag));
+	assert_eq!(niche.size, 1);
+	assert_eq!(read_niche(&Foo::Opt2, def), 2);
+	assert_eq!(read_niche(&Foo::Opt3(&0), def), 3);
+}
+
+#[test]
+fn enum_tagged_has_no_niche() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1(u8),
+		Opt2,
+	}
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(u8)]
+	enum Bar {
+		Opt1(bool),
+		Opt2,
+	}
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(type_defs[0].to_enum().unwrap().niche, None);
+
+	let type_defs = inspect::<Bar>();
+	assert_eq!(type_defs[0].to_enum().unwrap().niche, None);
+}
+
+/// Read niche of an enum value from raw memory
+fn read_niche<T>(value: &T, def: &DefEnum) -> u128 {
+	let niche = def.niche.as_ref().unwrap();
+	let ptr = value as *const T as *const u8;
+	unsafe {
+		let ptr = ptr.add(niche.offset);
+		match niche.size {
+			1 => *ptr as u128,
+			2 => (ptr as *const u16).read_unaligned() as u128,
+			4 => (ptr as *const u32).read_unaligned() as u128,
+			8 => (ptr as *const u64).read_unaligned() as u128,
+			_ => (ptr as *const u128).read_unaligned(),
+		}
+	}
+}
+
+#[test]
+fn enum_fieldless_with_discriminant_expressions() {
+	const BASE: isize = 100;
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1 = 1 << 3,
+		Opt2 = BASE,
+		Opt3,
+		Opt4 = size_of::<u32>() as isize,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let discriminants = get_discriminants(&type_defs[0]);
+	assert_eq!(discriminants, vec![8, 100, 101, 4]);
+	assert_eq!(discriminants[0], Foo::Opt1 as i128);
+	assert_eq!(discriminants[3], Foo::Opt4 as i128);
+}
+
+#[test]
+fn enum_negative_discriminants() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1 = -1,
+		Opt2,
+		Opt3 = -100,
+	}
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(i8)]
+	enum Bar {
+		Opt1 = i8::MIN,
+		Opt2 = i8::MAX,
+	}
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(get_discriminants(&type_defs[0]), vec![-1, 0, -100]);
+
+	let type_defs = inspect::<Bar>();
+	assert_eq!(get_discriminants(&type_defs[0]), vec![-128, 127]);
+
+	// Check discriminants are correct
+	let to_i8 = |bar: Bar| -> i8 { unsafe { transmute(bar) } };
+	assert_eq!(to_i8(Bar::Opt1), -128);
+	assert_eq!(to_i8(Bar::Opt2), 127);
+}
+
+// `#[repr(u128)]` is unstable
+#[cfg(feature = "nightly")]
+#[test]
+fn enum_u128_discriminants() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(u128)]
+	enum Foo {
+		Opt1 = 1,
+		Opt2 = u128::MAX,
+	}
+
+	// Discriminant above `i128::MAX` is stored with same bits
+	let type_defs = inspect::<Option<Foo>>();
+	let foo_def = &type_defs[1];
+	assert_eq!(get_discriminants(foo_def), vec![1, -1]);
+	assert_eq!(get_discriminants(foo_def)[1] as u128, Foo::Opt2 as u128);
+
+	// rustc also compares discriminants as `i128`, so valid range of tag is
+	// `u128::MAX..=1` (wrapping), and `None` is 2
+	let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+	let none: Option<Foo> = None;
+	let tag = unsafe { (&none as *const Option<Foo> as *const u128).read_unaligned() };
+	assert_eq!(niche.values[0].value, tag);
+	assert_eq!(tag, 2);
+}
+
+#[test]
+fn enum_negative_discriminants_niche() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(i8)]
+	enum Foo {
+		Opt1 = -2,
+		Opt2,
+		Opt3,
+	}
+
+	// Valid range of tag is `-2..=0`, so `None` is 1
+	let type_defs = inspect::<Option<Foo>>();
+	let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+	assert_eq!(niche.values[0].value, 1);
+
+	let none: Option<Foo> = None;
+	let byte = unsafe { *(&none as *const Option<Foo> as *const u8) };
+	assert_eq!(byte, 1);
+}
+
+#[test]
+fn enum_fieldful_with_discriminants() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(u16)]
+	enum Foo {
+		Opt1(u8) = 10,
+		Opt2 { value: u32 } = 1 << 8,
+		Opt3,
+	}
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(get_discriminants(&type_defs[0]), vec![10, 256, 257]);
+
+	// Read tag from raw memory
+	let value = Foo::Opt2 { value: 1 };
+	let tag = unsafe { (&value as *const Foo as *const u16).read() };
+	assert_eq!(tag, 256);
+}
+
+#[test]
+fn enum_u64_discriminant() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(u64)]
+	enum Foo {
+		Opt1 = 0,
+		Opt2 = u64::MAX,
+	}
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(get_discriminants(&type_defs[0]), vec![0, u64::MAX as i128]);
+}
+
+fn get_discriminants(enum_def: &DefType) -> Vec<i128> {
+	enum_def
+		.to_enum()
+		.unwrap()
+		.variants
+		.iter()
+		.map(|variant| variant.discriminant)
+		.collect()
+}
+
 fn get_variant_ids(enum_def: &DefType) -> Vec<Option<usize>> {
 	enum_def
 		.to_enum()
 		.unwrap()
 		.variants
 		.iter()
-		.map(|variant| variant.value_type_id.map(|id| id as usize))
+		.map(|variant| {
+			variant
+				.payload
+				.fields()
+				.first()
+				.map(|field| field.type_id as usize)
+		})
 		.collect()
 }
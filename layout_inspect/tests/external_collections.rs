@@ -0,0 +1,162 @@
+#![cfg(any(
+	feature = "smallvec",
+	feature = "arrayvec",
+	feature = "indexmap",
+	feature = "hashbrown"
+))]
+
+use std::mem::{align_of, size_of};
+
+#[allow(unused_imports)]
+use layout_inspect::{
+	defs::{DefInlineString, DefInlineVec, DefMap, DefSet, DefType},
+	inspect,
+};
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn small_vec() {
+	use smallvec::SmallVec;
+
+	let type_defs = inspect::<SmallVec<[u16; 4]>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::InlineVec(DefInlineVec {
+			name: "SmallVec<[u16; 4]>".to_string(),
+			size: size_of::<SmallVec<[u16; 4]>>(),
+			align: align_of::<SmallVec<[u16; 4]>>(),
+			value_type_id: 1,
+			inline_capacity: 4,
+			spills_to_heap: true,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "u16");
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn array_vec() {
+	use arrayvec::ArrayVec;
+
+	let type_defs = inspect::<ArrayVec<u32, 8>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::InlineVec(DefInlineVec {
+			name: "ArrayVec<u32,8>".to_string(),
+			size: size_of::<ArrayVec<u32, 8>>(),
+			align: align_of::<ArrayVec<u32, 8>>(),
+			value_type_id: 1,
+			inline_capacity: 8,
+			spills_to_heap: false,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "u32");
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn array_string() {
+	use arrayvec::ArrayString;
+
+	assert_eq!(
+		inspect::<ArrayString<16>>()[0],
+		DefType::InlineString(DefInlineString {
+			name: "ArrayString<16>".to_string(),
+			size: size_of::<ArrayString<16>>(),
+			align: align_of::<ArrayString<16>>(),
+			inline_capacity: 16,
+			spills_to_heap: false,
+		})
+	);
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn index_map_and_set() {
+	use indexmap::{IndexMap, IndexSet};
+
+	let type_defs = inspect::<(IndexMap<u8, String>, IndexSet<u8>)>();
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Map(DefMap {
+			name: "IndexMap<u8,String>".to_string(),
+			size: size_of::<IndexMap<u8, String>>(),
+			align: align_of::<IndexMap<u8, String>>(),
+			key_type_id: 2,
+			value_type_id: 3,
+		})
+	);
+	assert_eq!(
+		&type_defs[4],
+		&DefType::Set(DefSet {
+			name: "IndexSet<u8>".to_string(),
+			size: size_of::<IndexSet<u8>>(),
+			align: align_of::<IndexSet<u8>>(),
+			value_type_id: 2,
+		})
+	);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown_map() {
+	let type_defs = inspect::<hashbrown::HashMap<u64, bool>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Map(DefMap {
+			name: "hashbrown::HashMap<u64,bool>".to_string(),
+			size: size_of::<hashbrown::HashMap<u64, bool>>(),
+			align: align_of::<hashbrown::HashMap<u64, bool>>(),
+			key_type_id: 1,
+			value_type_id: 2,
+		})
+	);
+}
+
+#[cfg(any(feature = "indexmap", feature = "hashbrown"))]
+#[test]
+fn external_custom_hasher() {
+	use std::{any, collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+	type Hasher = BuildHasherDefault<DefaultHasher>;
+	let hasher = any::type_name::<Hasher>();
+
+	#[cfg(feature = "indexmap")]
+	{
+		use indexmap::{IndexMap, IndexSet};
+
+		let type_defs = inspect::<(IndexMap<u8, u8, Hasher>, IndexSet<u8, Hasher>)>();
+		assert_eq!(type_defs[1].name(), format!("IndexMap<u8,u8,{hasher}>"));
+		assert_eq!(type_defs[3].name(), format!("IndexSet<u8,{hasher}>"));
+	}
+
+	#[cfg(feature = "hashbrown")]
+	{
+		use std::hash::RandomState;
+
+		use hashbrown::{HashMap, HashSet};
+
+		let type_defs = inspect::<(
+			HashMap<u8, u8, Hasher>,
+			HashSet<u8, Hasher>,
+			HashMap<u8, u8, RandomState>,
+		)>();
+		let random_state = any::type_name::<RandomState>();
+		assert_eq!(
+			type_defs[1].name(),
+			format!("hashbrown::HashMap<u8,u8,{hasher}>")
+		);
+		assert_eq!(
+			type_defs[3].name(),
+			format!("hashbrown::HashSet<u8,{hasher}>")
+		);
+		assert_eq!(
+			type_defs[4].name(),
+			format!("hashbrown::HashMap<u8,u8,{random_state}>")
+		);
+	}
+}
@@ -0,0 +1,91 @@
+#![cfg(any(
+	feature = "compact_str",
+	feature = "smol_str",
+	feature = "bytes",
+	feature = "uuid"
+))]
+
+use std::mem::{align_of, size_of};
+
+#[allow(unused_imports)]
+use layout_inspect::{
+	defs::{DefInlineString, DefSeq, DefTransparent, DefType},
+	inspect,
+};
+
+#[cfg(feature = "compact_str")]
+#[test]
+fn compact_string() {
+	use compact_str::CompactString;
+
+	let type_defs = inspect::<CompactString>();
+	let def = type_defs[0].to_inline_string().unwrap();
+
+	assert_eq!(def.name, "CompactString");
+	assert_eq!(def.size, size_of::<CompactString>());
+	assert_eq!(def.align, align_of::<CompactString>());
+	assert!(def.spills_to_heap);
+
+	let capacity = def.inline_capacity;
+	assert!(capacity > 0);
+	assert!(!CompactString::new("a".repeat(capacity)).is_heap_allocated());
+	assert!(CompactString::new("a".repeat(capacity + 1)).is_heap_allocated());
+}
+
+#[cfg(feature = "smol_str")]
+#[test]
+fn smol_str() {
+	use smol_str::SmolStr;
+
+	let type_defs = inspect::<SmolStr>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::InlineString(DefInlineString {
+			name: "SmolStr".to_string(),
+			size: size_of::<SmolStr>(),
+			align: align_of::<SmolStr>(),
+			inline_capacity: 23,
+			spills_to_heap: true,
+		})
+	);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+	use bytes::{Bytes, BytesMut};
+
+	let type_defs = inspect::<(Bytes, BytesMut)>();
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Seq(DefSeq {
+			name: "Bytes".to_string(),
+			size: size_of::<Bytes>(),
+			align: align_of::<Bytes>(),
+			value_type_id: 2,
+		})
+	);
+	assert_eq!(type_defs[2].name(), "u8");
+	assert_eq!(type_defs[3].name(), "BytesMut");
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid() {
+	use uuid::Uuid;
+
+	let type_defs = inspect::<Uuid>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Transparent(DefTransparent {
+			name: "Uuid".to_string(),
+			size: 16,
+			align: 1,
+			value_type_id: 1,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "[u8; 16]");
+}
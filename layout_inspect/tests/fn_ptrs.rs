@@ -0,0 +1,126 @@
+use std::mem::{align_of, size_of};
+
+use layout_inspect::{
+	defs::{DefFnPtr, DefNiche, DefNicheValue, DefType},
+	inspect, Inspect,
+};
+
+#[test]
+fn fn_ptr() {
+	let type_defs = inspect::<fn(u8, u16) -> u32>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::FnPtr(DefFnPtr {
+			name: "fn(u8,u16) -> u32".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			arg_type_ids: vec![1, 2],
+			return_type_id: 3,
+			abi: "Rust".to_string(),
+			is_unsafe: false,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "u8");
+	assert_eq!(type_defs[2].name(), "u16");
+	assert_eq!(type_defs[3].name(), "u32");
+}
+
+#[test]
+fn fn_ptr_no_args_or_return() {
+	let type_defs = inspect::<fn()>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::FnPtr(DefFnPtr {
+			name: "fn()".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			arg_type_ids: vec![],
+			return_type_id: 1,
+			abi: "Rust".to_string(),
+			is_unsafe: false,
+		})
+	);
+	assert_eq!(type_defs[1].name(), "()");
+}
+
+#[test]
+fn unsafe_extern_c_fn_ptr() {
+	let type_defs = inspect::<unsafe extern "C" fn(*const u8) -> i32>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::FnPtr(DefFnPtr {
+			name: "unsafe extern \"C\" fn(*const u8) -> i32".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			arg_type_ids: vec![1],
+			return_type_id: 3,
+			abi: "C".to_string(),
+			is_unsafe: true,
+		})
+	);
+}
+
+#[test]
+fn fn_ptr_names() {
+	assert_eq!(<unsafe fn(u8)>::name(), "unsafe fn(u8)");
+	assert_eq!(
+		<extern "C" fn() -> bool>::name(),
+		"extern \"C\" fn() -> bool"
+	);
+}
+
+#[test]
+fn option_fn_ptr() {
+	let type_defs = inspect::<Option<fn()>>();
+
+	let def = type_defs[0].to_option().unwrap();
+	assert_eq!(def.size, size_of::<usize>());
+	assert_eq!(
+		def.niche,
+		Some(DefNiche {
+			offset: 0,
+			size: size_of::<usize>(),
+			untagged_variant: "Some".to_string(),
+			values: vec![DefNicheValue {
+				variant: "None".to_string(),
+				value: 0,
+			}],
+		})
+	);
+}
+
+#[derive(Inspect)]
+#[allow(dead_code)]
+#[repr(C)]
+struct Callbacks {
+	on_open: extern "C" fn(u32) -> bool,
+	on_close: Option<extern "C" fn()>,
+}
+
+#[test]
+fn struct_of_fn_ptrs() {
+	let type_defs = inspect::<Callbacks>();
+
+	let def = type_defs[0].to_struct().unwrap();
+	assert_eq!(def.fields.len(), 2);
+	assert_eq!(type_defs[1].name(), "extern \"C\" fn(u32) -> bool");
+	assert_eq!(type_defs[4].name(), "Option<extern \"C\" fn()>");
+}
+
+#[derive(Inspect)]
+#[allow(dead_code)]
+struct RefCallbacks<'a> {
+	on_event: fn(&'a mut u64, u32) -> bool,
+}
+
+#[test]
+fn fn_ptr_with_named_lifetime() {
+	let type_defs = inspect::<RefCallbacks>();
+
+	assert_eq!(type_defs[1].name(), "fn(&mut u64,u32) -> bool");
+	let def = type_defs[1].to_fn_ptr().unwrap();
+	assert_eq!(def.size, size_of::<usize>());
+}
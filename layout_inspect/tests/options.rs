@@ -1,9 +1,10 @@
 use std::mem::{align_of, size_of};
 
 use layout_inspect::{
-	defs::{DefOption, DefType},
+	defs::{DefNiche, DefNicheValue, DefOption, DefType},
 	inspect, Inspect,
 };
+use memoffset::{offset_of, offset_of_tuple};
 
 #[test]
 fn option_primitive() {
@@ -16,6 +17,7 @@ fn option_primitive() {
 			size: size_of::<u8>() * 2,
 			align: align_of::<u8>(),
 			value_type_id: 1,
+			niche: None,
 		})
 	);
 
@@ -33,6 +35,15 @@ fn option_primitive_with_niche() {
 			size: size_of::<bool>(),
 			align: align_of::<bool>(),
 			value_type_id: 1,
+			niche: Some(DefNiche {
+				offset: 0,
+				size: 1,
+				untagged_variant: "Some".to_string(),
+				values: vec![DefNicheValue {
+					variant: "None".to_string(),
+					value: 2,
+				}],
+			}),
 		})
 	);
 
@@ -56,6 +67,7 @@ fn option_struct() {
 			size: size_of::<Foo>() + align_of::<Foo>(),
 			align: align_of::<Foo>(),
 			value_type_id: 1,
+			niche: None,
 		})
 	);
 
@@ -81,6 +93,15 @@ fn option_struct_with_niche() {
 			size: size_of::<Foo>(),
 			align: align_of::<Foo>(),
 			value_type_id: 1,
+			niche: Some(DefNiche {
+				offset: offset_of!(Foo, big),
+				size: size_of::<NonZeroU32>(),
+				untagged_variant: "Some".to_string(),
+				values: vec![DefNicheValue {
+					variant: "None".to_string(),
+					value: 0,
+				}],
+			}),
 		})
 	);
 
@@ -98,6 +119,15 @@ fn option_box() {
 			size: size_of::<usize>(),
 			align: align_of::<usize>(),
 			value_type_id: 1,
+			niche: Some(DefNiche {
+				offset: 0,
+				size: size_of::<usize>(),
+				untagged_variant: "Some".to_string(),
+				values: vec![DefNicheValue {
+					variant: "None".to_string(),
+					value: 0,
+				}],
+			}),
 		})
 	);
 
@@ -108,15 +138,174 @@ fn option_box() {
 fn option_vec() {
 	let type_defs = inspect::<Option<Vec<u8>>>();
 
+	let def = type_defs[0].to_option().unwrap();
+	assert_eq!(def.name, "Option<Vec<u8>>");
+	assert_eq!(def.size, size_of::<usize>() * 3);
+	assert_eq!(def.align, align_of::<usize>());
+	assert_eq!(def.value_type_id, 1);
+
+	assert_eq!(type_defs[1].name(), "Vec<u8>");
+
+	// Niche is in pointer or capacity, depending on version of std
+	let niche = def.niche.as_ref().unwrap();
+	let vec_def = type_defs[1].to_vec().unwrap();
+	assert!(niche.offset == vec_def.ptr_offset || niche.offset == vec_def.cap_offset);
+	assert_eq!(niche.size, size_of::<usize>());
+
+	// Read niche from raw memory
+	let none: Option<Vec<u8>> = None;
+	let ptr = &none as *const Option<Vec<u8>> as *const u8;
+	let value = unsafe { (ptr.add(niche.offset) as *const usize).read() };
+	assert_eq!(value as u128, niche.values[0].value);
+}
+
+#[test]
+fn option_fat_box() {
+	let type_defs = inspect::<Option<Box<[u16]>>>();
+
+	let def = type_defs[0].to_option().unwrap();
+	assert_eq!(def.size, size_of::<usize>() * 2);
+
+	// Niche is in data pointer of the fat pointer
+	let niche = def.niche.as_ref().unwrap();
+	let box_def = type_defs[1].to_box().unwrap();
+	assert_eq!(niche.offset, box_def.ptr_offset);
+	assert_eq!(niche.size, size_of::<usize>());
+	assert_eq!(niche.values[0].value, 0);
+
+	// Read data pointer and length from raw memory
+	let some: Option<Box<[u16]>> = Some(Box::new([1, 2, 3]));
+	let ptr = &some as *const Option<Box<[u16]>> as *const u8;
+	let read_word = |offset: usize| unsafe { (ptr.add(offset) as *const usize).read() };
+	let slice = some.as_ref().unwrap();
+	assert_eq!(read_word(box_def.ptr_offset), slice.as_ptr() as usize);
+	assert_eq!(read_word(box_def.metadata_offset.unwrap()), 3);
+
+	let none: Option<Box<[u16]>> = None;
+	let ptr = &none as *const Option<Box<[u16]>> as *const u8;
+	let value = unsafe { (ptr.add(niche.offset) as *const usize).read() };
+	assert_eq!(value as u128, niche.values[0].value);
+}
+
+#[test]
+fn option_niche_values() {
+	use std::num::NonZeroU8;
+
+	let niche_value = |type_defs: &[DefType]| {
+		let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+		(niche.offset, niche.size, niche.values[0].value)
+	};
+
+	assert_eq!(niche_value(&inspect::<Option<NonZeroU8>>()), (0, 1, 0));
+	assert_eq!(niche_value(&inspect::<Option<char>>()), (0, 4, 0x110000));
 	assert_eq!(
-		&type_defs[0],
-		&DefType::Option(DefOption {
-			name: "Option<Vec<u8>>".to_string(),
-			size: size_of::<usize>() * 3,
-			align: align_of::<usize>(),
-			value_type_id: 1,
-		})
+		niche_value(&inspect::<Option<&u64>>()),
+		(0, size_of::<usize>(), 0)
+	);
+	assert_eq!(niche_value(&inspect::<Option<[bool; 2]>>()), (0, 1, 2));
+	assert_eq!(
+		niche_value(&inspect::<Option<(u32, bool)>>()),
+		(offset_of_tuple!((u32, bool), 1), 1, 2)
 	);
 
-	assert_eq!(type_defs[1].name(), "Vec<u8>");
+	// Outer `Option` uses values left over by inner `Option`
+	assert_eq!(niche_value(&inspect::<Option<Option<bool>>>()), (0, 1, 3));
+	// Inner `Option` has a tag, which outer `Option` uses as niche
+	assert_eq!(niche_value(&inspect::<Option<Option<u32>>>()), (0, 4, 2));
+}
+
+#[test]
+fn option_struct_with_bool_niche() {
+	#[derive(Inspect)]
+	struct Foo {
+		num: u32,
+		flag: bool,
+	}
+
+	let type_defs = inspect::<Option<Foo>>();
+	let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+
+	assert_eq!(
+		niche,
+		&DefNiche {
+			offset: offset_of!(Foo, flag),
+			size: 1,
+			untagged_variant: "Some".to_string(),
+			values: vec![DefNicheValue {
+				variant: "None".to_string(),
+				value: 2,
+			}],
+		}
+	);
+
+	// Read niche from raw memory
+	let none: Option<Foo> = None;
+	let ptr = &none as *const Option<Foo> as *const u8;
+	assert_eq!(unsafe { *ptr.add(niche.offset) }, 2);
+
+	let some = Some(Foo {
+		num: 123,
+		flag: true,
+	});
+	let ptr = &some as *const Option<Foo> as *const u8;
+	assert_eq!(unsafe { *ptr.add(niche.offset) }, 1);
+}
+
+#[test]
+fn option_enum_niche() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo {
+		Opt1,
+		Opt2,
+		Opt3,
+	}
+
+	let type_defs = inspect::<Option<Foo>>();
+	let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+	assert_eq!((niche.offset, niche.size), (0, 1));
+	assert_eq!(niche.values[0].value, 3);
+
+	let none: Option<Foo> = None;
+	let byte = unsafe { *(&none as *const Option<Foo> as *const u8) };
+	assert_eq!(byte, 3);
+}
+
+#[test]
+fn option_aligned_enum_niche() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(align(8))]
+	enum Foo {
+		Opt1,
+		Opt2,
+		Opt3,
+	}
+
+	// Only first byte holds discriminant. Rest is padding.
+	let type_defs = inspect::<Option<Foo>>();
+	let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+	assert_eq!((niche.offset, niche.size), (0, 1));
+	assert_eq!(niche.values[0].value, 3);
+
+	let none: Option<Foo> = None;
+	let byte = unsafe { *(&none as *const Option<Foo> as *const u8) };
+	assert_eq!(byte, 3);
+}
+
+#[test]
+fn option_fat_ref() {
+	let type_defs = inspect::<Option<&str>>();
+
+	// Niche is in data pointer of the fat pointer
+	let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+	let str_def = type_defs[1].to_str_slice().unwrap();
+	assert_eq!(niche.offset, str_def.ptr_offset);
+	assert_eq!(niche.size, size_of::<usize>());
+
+	let s = "abc";
+	let some = Some(s);
+	let ptr = &some as *const Option<&str> as *const u8;
+	let data_ptr = unsafe { (ptr.add(niche.offset) as *const usize).read() };
+	assert_eq!(data_ptr, s.as_ptr() as usize);
 }
@@ -0,0 +1,176 @@
+use std::{
+	cmp::Ordering,
+	ffi::{CStr, CString, OsStr, OsString},
+	mem::{align_of, size_of, transmute, transmute_copy},
+	net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+	path::{Path, PathBuf},
+	time::{Duration, Instant, SystemTime},
+};
+
+use layout_inspect::{
+	defs::{DefDuration, DefEnumTagLayout, DefLogicalType, DefOpaque, DefRef, DefType},
+	inspect, Inspect,
+};
+
+#[test]
+fn path_buf() {
+	let type_defs = inspect::<PathBuf>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Opaque(DefOpaque {
+			name: "PathBuf".to_string(),
+			size: Some(size_of::<PathBuf>()),
+			align: Some(align_of::<PathBuf>()),
+			logical_type: DefLogicalType::Path,
+		})
+	);
+}
+
+#[test]
+fn path_ref() {
+	let type_defs = inspect::<&Path>();
+
+	// Find which word of a real `&Path` holds the length
+	let path = Path::new("abc");
+	let words: [usize; 2] = unsafe { transmute_copy(&path) };
+	let (ptr_offset, len_offset) = if words[1] == 3 {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	};
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ref(DefRef {
+			name: "&Path".to_string(),
+			size: size_of::<&Path>(),
+			align: align_of::<&Path>(),
+			value_type_id: 1,
+			mutable: false,
+			fat: true,
+			ptr_offset,
+			metadata_offset: Some(len_offset),
+		})
+	);
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Opaque(DefOpaque {
+			name: "Path".to_string(),
+			size: None,
+			align: Some(1),
+			logical_type: DefLogicalType::Path,
+		})
+	);
+}
+
+#[test]
+fn os_and_c_strings() {
+	let type_defs = inspect::<(OsString, &OsStr, CString, Box<CStr>)>();
+
+	let opaques = type_defs
+		.iter()
+		.filter_map(|def| def.to_opaque())
+		.map(|def| (&def.name[..], def.size, &def.logical_type))
+		.collect::<Vec<_>>();
+	assert_eq!(
+		opaques,
+		[
+			(
+				"OsString",
+				Some(size_of::<OsString>()),
+				&DefLogicalType::OsStr
+			),
+			("OsStr", None, &DefLogicalType::OsStr),
+			("CString", Some(size_of::<CString>()), &DefLogicalType::CStr),
+			("CStr", None, &DefLogicalType::CStr),
+		]
+	);
+}
+
+#[test]
+fn time_and_net_types() {
+	assert_eq!(
+		inspect::<Instant>()[0],
+		DefType::Opaque(DefOpaque {
+			name: "Instant".to_string(),
+			size: Some(size_of::<Instant>()),
+			align: Some(align_of::<Instant>()),
+			logical_type: DefLogicalType::Instant,
+		})
+	);
+	assert_eq!(
+		inspect::<SystemTime>()[0].to_opaque().unwrap().logical_type,
+		DefLogicalType::SystemTime
+	);
+	assert_eq!(
+		inspect::<IpAddr>()[0].to_opaque().unwrap().logical_type,
+		DefLogicalType::IpAddr
+	);
+	assert_eq!(Ipv4Addr::name(), "Ipv4Addr");
+	assert_eq!(Ipv6Addr::size(), Some(16));
+	assert_eq!(SocketAddr::name(), "SocketAddr");
+	assert_eq!(SocketAddrV4::name(), "SocketAddrV4");
+	assert_eq!(SocketAddrV6::name(), "SocketAddrV6");
+}
+
+#[test]
+fn duration() {
+	let type_defs = inspect::<Duration>();
+	let def = type_defs[0].to_duration().unwrap();
+
+	assert_eq!(def.name, "Duration");
+	assert_eq!(def.size, size_of::<Duration>());
+	assert_eq!(def.align, align_of::<Duration>());
+
+	let duration = Duration::new(1234, 5678);
+	let ptr = &duration as *const Duration as *const u8;
+	let secs_offset = def.secs_offset.unwrap();
+	let nanos_offset = def.nanos_offset.unwrap();
+	// SAFETY: Offsets are of the 2 fields
+	let secs = unsafe { (ptr.add(secs_offset) as *const u64).read_unaligned() };
+	let nanos = unsafe { (ptr.add(nanos_offset) as *const u32).read_unaligned() };
+	assert_eq!((secs, nanos), (1234, 5678));
+}
+
+#[test]
+fn option_duration_niche() {
+	let type_defs = inspect::<Option<Duration>>();
+	let DefType::Duration(DefDuration { nanos_offset, .. }) = &type_defs[1] else {
+		panic!("not a Duration");
+	};
+
+	let def = type_defs[0].to_option().unwrap();
+	assert_eq!(
+		def.niche.is_some(),
+		size_of::<Option<Duration>>() == size_of::<Duration>()
+	);
+	if let Some(niche) = &def.niche {
+		assert_eq!(Some(niche.offset), *nanos_offset);
+		assert_eq!(niche.size, 4);
+		assert_eq!(niche.values[0].value, 1_000_000_000);
+	}
+}
+
+#[test]
+fn ordering() {
+	let type_defs = inspect::<Ordering>();
+	let def = type_defs[0].to_enum().unwrap();
+
+	assert_eq!(def.name, "Ordering");
+	assert_eq!(def.size, 1);
+	assert_eq!(def.repr.int.as_deref(), Some("i8"));
+	assert_eq!(
+		def.tag_layout,
+		Some(DefEnumTagLayout { offset: 0, size: 1 })
+	);
+	let variants = def
+		.variants
+		.iter()
+		.map(|variant| (&variant.name[..], variant.discriminant))
+		.collect::<Vec<_>>();
+	assert_eq!(variants, [("Less", -1), ("Equal", 0), ("Greater", 1)]);
+
+	// SAFETY: `Ordering` is `#[repr(i8)]`
+	assert_eq!(unsafe { transmute::<Ordering, i8>(Ordering::Less) }, -1);
+}
@@ -1,20 +1,27 @@
-use std::mem::{align_of, size_of};
+use std::mem::{align_of, size_of, transmute_copy};
 
 use layout_inspect::{
 	defs::{
-		DefPhantomData, DefResult, DefStr, DefStrSlice, DefString, DefStruct, DefStructField, DefType,
+		DefNiche, DefNicheValue, DefPhantomData, DefRepr, DefResult, DefStr, DefStrSlice, DefString,
+		DefStruct, DefStructField, DefType,
 	},
 	inspect, Inspect,
 };
 
 #[test]
 fn string() {
+	let type_defs = inspect::<String>();
+	let def = type_defs[0].to_string().unwrap();
+
 	assert_eq!(
-		inspect::<String>()[0],
+		type_defs[0],
 		DefType::String(DefString {
 			name: "String".to_string(),
 			size: size_of::<String>(),
 			align: align_of::<String>(),
+			ptr_offset: def.ptr_offset,
+			cap_offset: def.cap_offset,
+			len_offset: def.len_offset,
 		})
 	);
 }
@@ -33,12 +40,23 @@ fn str() {
 
 #[test]
 fn str_slice() {
+	// Find which word of a real `&str` holds the length
+	let s = "abc";
+	let words: [usize; 2] = unsafe { transmute_copy(&s) };
+	let (ptr_offset, len_offset) = if words[1] == s.len() {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	};
+
 	assert_eq!(
 		inspect::<&str>()[0],
 		DefType::StrSlice(DefStrSlice {
 			name: "&str".to_string(),
 			size: size_of::<&str>(),
 			align: align_of::<&str>(),
+			ptr_offset,
+			len_offset,
 		})
 	);
 
@@ -77,6 +95,7 @@ fn str_slice() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -86,6 +105,8 @@ fn str_slice() {
 			name: "&str".to_string(),
 			size: size_of::<&str>(),
 			align: align_of::<&str>(),
+			ptr_offset,
+			len_offset,
 		})
 	);
 }
@@ -121,9 +142,53 @@ fn result() {
 			align: align_of::<Result<u8, u16>>(),
 			ok_type_id: 1,
 			err_type_id: 2,
+			niche: None,
 		})
 	);
 
 	assert_eq!(type_defs[1].name(), "u8");
 	assert_eq!(type_defs[2].name(), "u16");
 }
+
+#[test]
+fn result_niche() {
+	use std::num::NonZeroU8;
+
+	// `Ok` is larger, so `Err` is stored in niche of `Ok`
+	let type_defs = inspect::<Result<NonZeroU8, ()>>();
+	assert_eq!(
+		type_defs[0].to_result().unwrap().niche,
+		Some(DefNiche {
+			offset: 0,
+			size: 1,
+			untagged_variant: "Ok".to_string(),
+			values: vec![DefNicheValue {
+				variant: "Err".to_string(),
+				value: 0,
+			}],
+		})
+	);
+
+	let err: Result<NonZeroU8, ()> = Err(());
+	let byte = unsafe { *(&err as *const Result<NonZeroU8, ()> as *const u8) };
+	assert_eq!(byte, 0);
+
+	// `Err` is larger, so `Ok` is stored in niche of `Err`
+	let type_defs = inspect::<Result<(), bool>>();
+	assert_eq!(
+		type_defs[0].to_result().unwrap().niche,
+		Some(DefNiche {
+			offset: 0,
+			size: 1,
+			untagged_variant: "Err".to_string(),
+			values: vec![DefNicheValue {
+				variant: "Ok".to_string(),
+				value: 2,
+			}],
+		})
+	);
+
+	let ok: Result<(), bool> = Ok(());
+	let byte = unsafe { *(&ok as *const Result<(), bool> as *const u8) };
+	assert_eq!(byte, 2);
+}
@@ -1,7 +1,9 @@
+#![cfg_attr(feature = "nightly", feature(never_type))]
+
 use std::mem::{align_of, size_of};
 
 use layout_inspect::{
-	defs::{DefPrimitive, DefType},
+	defs::{DefPrimitive, DefPrimitiveKind, DefType, DefValidRange},
 	inspect,
 };
 
@@ -13,6 +15,11 @@ fn u8() {
 			name: "u8".to_string(),
 			size: size_of::<u8>(),
 			align: align_of::<u8>(),
+			kind: DefPrimitiveKind::UnsignedInt,
+			valid_ranges: vec![DefValidRange {
+				start: 0,
+				end: u8::MAX as u128,
+			}],
 		})
 	);
 }
@@ -25,6 +32,11 @@ fn u32() {
 			name: "u32".to_string(),
 			size: size_of::<u32>(),
 			align: align_of::<u32>(),
+			kind: DefPrimitiveKind::UnsignedInt,
+			valid_ranges: vec![DefValidRange {
+				start: 0,
+				end: u32::MAX as u128,
+			}],
 		})
 	);
 }
@@ -37,6 +49,11 @@ fn u128() {
 			name: "u128".to_string(),
 			size: size_of::<u128>(),
 			align: align_of::<u128>(),
+			kind: DefPrimitiveKind::UnsignedInt,
+			valid_ranges: vec![DefValidRange {
+				start: 0,
+				end: u128::MAX,
+			}],
 		})
 	);
 }
@@ -49,6 +66,11 @@ fn i8() {
 			name: "i8".to_string(),
 			size: size_of::<i8>(),
 			align: align_of::<i8>(),
+			kind: DefPrimitiveKind::SignedInt,
+			valid_ranges: vec![DefValidRange {
+				start: 0,
+				end: u8::MAX as u128,
+			}],
 		})
 	);
 }
@@ -61,6 +83,11 @@ fn f64() {
 			name: "f64".to_string(),
 			size: size_of::<f64>(),
 			align: align_of::<f64>(),
+			kind: DefPrimitiveKind::Float,
+			valid_ranges: vec![DefValidRange {
+				start: 0,
+				end: u64::MAX as u128,
+			}],
 		})
 	);
 }
@@ -73,6 +100,11 @@ fn usize() {
 			name: "usize".to_string(),
 			size: size_of::<usize>(),
 			align: align_of::<usize>(),
+			kind: DefPrimitiveKind::UnsignedInt,
+			valid_ranges: vec![DefValidRange {
+				start: 0,
+				end: usize::MAX as u128,
+			}],
 		})
 	);
 }
@@ -85,6 +117,11 @@ fn isize() {
 			name: "isize".to_string(),
 			size: size_of::<isize>(),
 			align: align_of::<isize>(),
+			kind: DefPrimitiveKind::SignedInt,
+			valid_ranges: vec![DefValidRange {
+				start: 0,
+				end: usize::MAX as u128,
+			}],
 		})
 	);
 }
@@ -98,6 +135,8 @@ fn non_zero_u8() {
 			name: "NonZeroU8".to_string(),
 			size: size_of::<NonZeroU8>(),
 			align: align_of::<NonZeroU8>(),
+			kind: DefPrimitiveKind::UnsignedInt,
+			valid_ranges: vec![DefValidRange { start: 1, end: 255 }],
 		})
 	);
 }
@@ -110,6 +149,8 @@ fn bool() {
 			name: "bool".to_string(),
 			size: size_of::<bool>(),
 			align: align_of::<bool>(),
+			kind: DefPrimitiveKind::Bool,
+			valid_ranges: vec![DefValidRange { start: 0, end: 1 }],
 		})
 	);
 }
@@ -122,6 +163,17 @@ fn char() {
 			name: "char".to_string(),
 			size: size_of::<char>(),
 			align: align_of::<char>(),
+			kind: DefPrimitiveKind::Char,
+			valid_ranges: vec![
+				DefValidRange {
+					start: 0,
+					end: 0xd7ff,
+				},
+				DefValidRange {
+					start: 0xe000,
+					end: 0x10ffff,
+				},
+			],
 		})
 	);
 }
@@ -134,6 +186,67 @@ fn unit() {
 			name: "()".to_string(),
 			size: 0,
 			align: 1,
+			kind: DefPrimitiveKind::Unit,
+			valid_ranges: vec![DefValidRange { start: 0, end: 0 }],
+		})
+	);
+}
+
+#[test]
+fn valid_ranges() {
+	use std::num::{NonZeroI16, NonZeroU64};
+
+	let valid_ranges = |type_defs: Vec<DefType>| {
+		match type_defs.into_iter().next() {
+			Some(DefType::Primitive(def)) => (def.kind, def.valid_ranges),
+			_ => panic!("Expected primitive"),
+		}
+	};
+
+	assert_eq!(
+		valid_ranges(inspect::<NonZeroU64>()),
+		(
+			DefPrimitiveKind::UnsignedInt,
+			vec![DefValidRange {
+				start: 1,
+				end: u64::MAX as u128
+			}]
+		)
+	);
+	// Signed ints' ranges are bit patterns, read as unsigned
+	assert_eq!(
+		valid_ranges(inspect::<NonZeroI16>()),
+		(
+			DefPrimitiveKind::SignedInt,
+			vec![DefValidRange {
+				start: 1,
+				end: u16::MAX as u128
+			}]
+		)
+	);
+	assert_eq!(
+		valid_ranges(inspect::<f32>()),
+		(
+			DefPrimitiveKind::Float,
+			vec![DefValidRange {
+				start: 0,
+				end: u32::MAX as u128
+			}]
+		)
+	);
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn never() {
+	assert_eq!(
+		inspect::<!>()[0],
+		DefType::Primitive(DefPrimitive {
+			name: "!".to_string(),
+			size: 0,
+			align: 1,
+			kind: DefPrimitiveKind::Never,
+			valid_ranges: vec![],
 		})
 	);
 }
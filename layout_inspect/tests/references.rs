@@ -0,0 +1,250 @@
+#![cfg_attr(
+	feature = "nightly_offset_of_enum",
+	feature(offset_of_enum, offset_of_nested)
+)]
+
+use std::{
+	mem::{align_of, size_of, transmute_copy},
+	ptr::NonNull,
+};
+
+use layout_inspect::{
+	defs::{DefPtr, DefRef, DefRepr, DefStruct, DefStructField, DefType},
+	inspect, Inspect,
+};
+use memoffset::offset_of;
+
+#[test]
+fn reference() {
+	let type_defs = inspect::<&u32>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ref(DefRef {
+			name: "&u32".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			mutable: false,
+			fat: false,
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u32");
+}
+
+#[test]
+fn reference_mut() {
+	let type_defs = inspect::<&mut u32>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ref(DefRef {
+			name: "&mut u32".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			mutable: true,
+			fat: false,
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+}
+
+#[test]
+fn reference_to_reference() {
+	let type_defs = inspect::<&&u8>();
+
+	assert_eq!(type_defs[0].name(), "&&u8");
+	assert_eq!(type_defs[0].to_ref().unwrap().value_type_id, 1);
+	assert_eq!(type_defs[1].name(), "&u8");
+	assert_eq!(type_defs[2].name(), "u8");
+}
+
+#[test]
+fn const_pointer() {
+	let type_defs = inspect::<*const u16>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ptr(DefPtr {
+			name: "*const u16".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			mutable: false,
+			fat: false,
+			non_null: false,
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u16");
+}
+
+#[test]
+fn mut_pointer() {
+	let type_defs = inspect::<*mut u16>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ptr(DefPtr {
+			name: "*mut u16".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			mutable: true,
+			fat: false,
+			non_null: false,
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+}
+
+#[test]
+fn fat_pointer() {
+	let type_defs = inspect::<*const [u8]>();
+
+	// Find which word of pointer holds the length
+	let array = [0u8; 3];
+	let ptr: *const [u8] = &array[..];
+	let words: [usize; 2] = unsafe { transmute_copy(&ptr) };
+	let (ptr_offset, len_offset) = if words[1] == array.len() {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	};
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ptr(DefPtr {
+			name: "*const [u8]".to_string(),
+			size: size_of::<usize>() * 2,
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			mutable: false,
+			fat: true,
+			non_null: false,
+			ptr_offset,
+			metadata_offset: Some(len_offset),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "[u8]");
+}
+
+#[test]
+fn non_null() {
+	let type_defs = inspect::<NonNull<u64>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Ptr(DefPtr {
+			name: "NonNull<u64>".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			mutable: true,
+			fat: false,
+			non_null: true,
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+
+	let type_defs = inspect::<NonNull<str>>();
+	assert_eq!(type_defs[0].name(), "NonNull<str>");
+	assert!(type_defs[0].to_ptr().unwrap().fat);
+}
+
+#[test]
+fn struct_with_lifetime() {
+	#[derive(Inspect)]
+	struct Node<'a> {
+		value: u32,
+		parent: Option<&'a Node<'a>>,
+		children: Vec<&'a Node<'a>>,
+	}
+
+	let type_defs = inspect::<Node>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Struct(DefStruct {
+			name: "Node".to_string(),
+			ser_name: "Node".to_string(),
+			size: Some(size_of::<Node>()),
+			align: Some(align_of::<Node>()),
+			fields: vec![
+				DefStructField {
+					name: "value".to_string(),
+					ser_name: "value".to_string(),
+					type_id: 1,
+					offset: offset_of!(Node, value),
+					flatten: false,
+					skip: false,
+				},
+				DefStructField {
+					name: "parent".to_string(),
+					ser_name: "parent".to_string(),
+					type_id: 2,
+					offset: offset_of!(Node, parent),
+					flatten: false,
+					skip: false,
+				},
+				DefStructField {
+					name: "children".to_string(),
+					ser_name: "children".to_string(),
+					type_id: 4,
+					offset: offset_of!(Node, children),
+					flatten: false,
+					skip: false,
+				},
+			],
+			tag: None,
+			transparent: false,
+			repr: DefRepr::default(),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u32");
+	assert_eq!(type_defs[2].name(), "Option<&Node>");
+	assert_eq!(
+		&type_defs[3],
+		&DefType::Ref(DefRef {
+			name: "&Node".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 0,
+			mutable: false,
+			fat: false,
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+	assert_eq!(type_defs[4].name(), "Vec<&Node>");
+	assert_eq!(type_defs[4].to_vec().unwrap().value_type_id, 3);
+	assert_eq!(type_defs.len(), 5);
+}
+
+#[test]
+fn enum_with_lifetime() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	enum Foo<'a> {
+		Num(&'a u32),
+		Str(&'a str),
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	assert_eq!(type_defs[0].name(), "Foo");
+	assert_eq!(type_defs[1].name(), "&u32");
+	assert_eq!(type_defs[2].name(), "u32");
+	assert_eq!(type_defs[3].name(), "&str");
+}
@@ -0,0 +1,216 @@
+use std::{
+	mem::{align_of, size_of},
+	rc::{self, Rc},
+	sync::{self, Arc},
+};
+
+use layout_inspect::{
+	defs::{DefArc, DefArcWeak, DefRc, DefRcWeak, DefType},
+	inspect, Inspect,
+};
+
+#[test]
+fn rc() {
+	let type_defs = inspect::<Rc<u64>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Rc(DefRc {
+			name: "Rc<u64>".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			strong_offset: 0,
+			weak_offset: size_of::<usize>(),
+			value_offset: Some(size_of::<usize>() * 2),
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u64");
+}
+
+#[test]
+fn arc() {
+	let type_defs = inspect::<Arc<u8>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Arc(DefArc {
+			name: "Arc<u8>".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			strong_offset: 0,
+			weak_offset: size_of::<usize>(),
+			value_offset: Some(size_of::<usize>() * 2),
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u8");
+}
+
+#[test]
+fn rc_weak() {
+	let type_defs = inspect::<rc::Weak<u32>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::RcWeak(DefRcWeak {
+			name: "rc::Weak<u32>".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			strong_offset: 0,
+			weak_offset: size_of::<usize>(),
+			value_offset: Some(size_of::<usize>() * 2),
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u32");
+}
+
+#[test]
+fn arc_weak() {
+	let type_defs = inspect::<sync::Weak<u32>>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::ArcWeak(DefArcWeak {
+			name: "sync::Weak<u32>".to_string(),
+			size: size_of::<usize>(),
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			strong_offset: 0,
+			weak_offset: size_of::<usize>(),
+			value_offset: Some(size_of::<usize>() * 2),
+			ptr_offset: 0,
+			metadata_offset: None,
+		})
+	);
+
+	// `Weak`s are distinct types from `Rc`s / `Arc`s
+	let type_defs = inspect::<(Arc<u32>, sync::Weak<u32>, rc::Weak<u32>)>();
+	assert_eq!(type_defs[1].name(), "Arc<u32>");
+	assert_eq!(type_defs[3].name(), "sync::Weak<u32>");
+	assert_eq!(type_defs[4].name(), "rc::Weak<u32>");
+}
+
+#[test]
+fn rc_value_offset_high_alignment() {
+	#[derive(Inspect)]
+	#[repr(align(64))]
+	struct Aligned {
+		value: u8,
+	}
+
+	let type_defs = inspect::<Rc<Aligned>>();
+	let def = type_defs[0].to_rc().unwrap();
+	assert_eq!(def.value_offset, Some(64));
+}
+
+#[test]
+fn rc_allocation_from_raw_memory() {
+	#[derive(Inspect)]
+	struct Foo {
+		num: u32,
+		flag: bool,
+	}
+
+	let type_defs = inspect::<Rc<Foo>>();
+	let def = type_defs[0].to_rc().unwrap();
+
+	let rc = Rc::new(Foo {
+		num: 123,
+		flag: true,
+	});
+	let _clone1 = rc.clone();
+	let _clone2 = rc.clone();
+	let _weak = Rc::downgrade(&rc);
+
+	// Follow pointer to allocation, and read counts and value
+	let alloc_ptr = unsafe { *(&rc as *const Rc<Foo> as *const *const u8) };
+	unsafe {
+		assert_eq!(*(alloc_ptr.add(def.strong_offset) as *const usize), 3);
+		// Weak count includes 1 for all strong references
+		assert_eq!(*(alloc_ptr.add(def.weak_offset) as *const usize), 2);
+		let value_ptr = alloc_ptr.add(def.value_offset.unwrap());
+		assert_eq!(value_ptr as *const Foo, Rc::as_ptr(&rc));
+	}
+}
+
+#[test]
+fn arc_allocation_from_raw_memory() {
+	let type_defs = inspect::<Arc<u64>>();
+	let def = type_defs[0].to_arc().unwrap();
+
+	let arc = Arc::new(0x1234_5678_9abc_def0u64);
+	let _clone = arc.clone();
+
+	let alloc_ptr = unsafe { *(&arc as *const Arc<u64> as *const *const u8) };
+	unsafe {
+		assert_eq!(*(alloc_ptr.add(def.strong_offset) as *const usize), 2);
+		assert_eq!(*(alloc_ptr.add(def.weak_offset) as *const usize), 1);
+		assert_eq!(
+			*(alloc_ptr.add(def.value_offset.unwrap()) as *const u64),
+			0x1234_5678_9abc_def0
+		);
+	}
+}
+
+#[test]
+fn weak_count_includes_strong_pointers() {
+	let type_defs = inspect::<Rc<u32>>();
+	let rc_def = type_defs[0].to_rc().unwrap();
+	let type_defs = inspect::<Arc<u32>>();
+	let arc_def = type_defs[0].to_arc().unwrap();
+
+	let read_counts = |alloc_ptr: *const u8, strong_offset: usize, weak_offset: usize| unsafe {
+		(
+			*(alloc_ptr.add(strong_offset) as *const usize),
+			*(alloc_ptr.add(weak_offset) as *const usize),
+		)
+	};
+
+	// No `Weak`s: weak count is 1, held by strong pointers
+	let rc = Rc::new(1u32);
+	let alloc_ptr = unsafe { *(&rc as *const Rc<u32> as *const *const u8) };
+	let read = || read_counts(alloc_ptr, rc_def.strong_offset, rc_def.weak_offset);
+	assert_eq!(read(), (1, 1));
+
+	let weak = Rc::downgrade(&rc);
+	assert_eq!(read(), (1, 2));
+
+	// Dropping last strong pointer releases the weak count it held
+	drop(rc);
+	assert_eq!(read(), (0, 1));
+	drop(weak);
+
+	let arc = Arc::new(1u32);
+	let alloc_ptr = unsafe { *(&arc as *const Arc<u32> as *const *const u8) };
+	let read = || read_counts(alloc_ptr, arc_def.strong_offset, arc_def.weak_offset);
+	assert_eq!(read(), (1, 1));
+
+	let weak = Arc::downgrade(&arc);
+	assert_eq!(read(), (1, 2));
+
+	drop(arc);
+	assert_eq!(read(), (0, 1));
+	drop(weak);
+}
+
+#[test]
+fn rc_unsized_value_offset() {
+	let type_defs = inspect::<Rc<[u64]>>();
+	let def = type_defs[0].to_rc().unwrap();
+
+	let rc: Rc<[u64]> = Rc::from(vec![1, 2, 3]);
+	let alloc_ptr = unsafe { *(&rc as *const Rc<[u64]> as *const *const u8) };
+	let value_ptr = unsafe { alloc_ptr.add(def.value_offset.unwrap()) };
+	assert_eq!(value_ptr as *const u64, rc.as_ptr());
+}
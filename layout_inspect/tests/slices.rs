@@ -0,0 +1,172 @@
+use std::{
+	mem::{align_of, size_of, transmute_copy},
+	rc::Rc,
+	sync::Arc,
+};
+
+use layout_inspect::{
+	defs::{DefArc, DefBox, DefRc, DefSlice, DefSliceRef, DefType},
+	inspect,
+};
+
+#[test]
+fn slice() {
+	let type_defs = inspect::<[u32]>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Slice(DefSlice {
+			name: "[u32]".to_string(),
+			size: None,
+			align: align_of::<u32>(),
+			value_type_id: 1,
+		})
+	);
+
+	assert_eq!(type_defs[0].size(), None);
+	assert_eq!(type_defs[0].align(), Some(align_of::<u32>()));
+	assert_eq!(type_defs[1].name(), "u32");
+}
+
+#[test]
+fn slice_ref() {
+	let type_defs = inspect::<&[u16]>();
+
+	let def = type_defs[0].to_slice_ref().unwrap();
+	assert_eq!(def.name, "&[u16]");
+	assert_eq!(def.size, size_of::<usize>() * 2);
+	assert_eq!(def.align, align_of::<usize>());
+	assert_eq!(def.value_type_id, 1);
+	assert!(!def.mutable);
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Slice(DefSlice {
+			name: "[u16]".to_string(),
+			size: None,
+			align: align_of::<u16>(),
+			value_type_id: 2,
+		})
+	);
+	assert_eq!(type_defs[2].name(), "u16");
+}
+
+#[test]
+fn slice_ref_offsets() {
+	let type_defs = inspect::<&[u64]>();
+	let DefSliceRef {
+		ptr_offset,
+		len_offset,
+		..
+	} = *type_defs[0].to_slice_ref().unwrap();
+
+	assert_ne!(ptr_offset, len_offset);
+
+	// Read pointer and length from a real slice using the offsets
+	let arr = [1u64, 2, 3];
+	let slice: &[u64] = &arr[..];
+	let words: [usize; 2] = unsafe { std::mem::transmute(slice) };
+	let word_size = size_of::<usize>();
+	assert_eq!(words[ptr_offset / word_size], arr.as_ptr() as usize);
+	assert_eq!(words[len_offset / word_size], 3);
+}
+
+#[test]
+fn slice_ref_mut() {
+	let type_defs = inspect::<&mut [u8]>();
+
+	let def = type_defs[0].to_slice_ref().unwrap();
+	assert_eq!(def.name, "&mut [u8]");
+	assert_eq!(def.size, size_of::<&mut [u8]>());
+	assert!(def.mutable);
+	assert_eq!(type_defs[1].name(), "[u8]");
+}
+
+#[test]
+fn boxed_slice() {
+	let type_defs = inspect::<Box<[u8]>>();
+
+	let boxed: Box<[u8]> = Box::new([1, 2, 3]);
+	let (ptr_offset, len_offset) = fat_ptr_offsets(&boxed, boxed.as_ptr() as usize, 3);
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Box(DefBox {
+			name: "Box<[u8]>".to_string(),
+			size: size_of::<usize>() * 2,
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			ptr_offset,
+			metadata_offset: Some(len_offset),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "[u8]");
+	assert_eq!(type_defs[2].name(), "u8");
+}
+
+#[test]
+fn rc_str() {
+	let type_defs = inspect::<Rc<str>>();
+
+	// Data pointer points to allocation, so find it as the word which isn't
+	// the length
+	let rc: Rc<str> = Rc::from("abcde");
+	let (ptr_offset, len_offset) = fat_ptr_offsets(&rc, rc.as_ptr() as usize, 5);
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Rc(DefRc {
+			name: "Rc<str>".to_string(),
+			size: size_of::<usize>() * 2,
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			strong_offset: 0,
+			weak_offset: size_of::<usize>(),
+			value_offset: Some(size_of::<usize>() * 2),
+			ptr_offset,
+			metadata_offset: Some(len_offset),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "str");
+}
+
+#[test]
+fn arc_slice() {
+	let type_defs = inspect::<Arc<[u32]>>();
+
+	let arc: Arc<[u32]> = Arc::from(vec![1, 2, 3, 4, 5, 6, 7]);
+	let (ptr_offset, len_offset) = fat_ptr_offsets(&arc, arc.as_ptr() as usize, 7);
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Arc(DefArc {
+			name: "Arc<[u32]>".to_string(),
+			size: size_of::<usize>() * 2,
+			align: align_of::<usize>(),
+			value_type_id: 1,
+			strong_offset: 0,
+			weak_offset: size_of::<usize>(),
+			value_offset: Some(size_of::<usize>() * 2),
+			ptr_offset,
+			metadata_offset: Some(len_offset),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "[u32]");
+}
+
+/// Get offsets of data pointer and length within a fat pointer, by finding
+/// which word contains the length. `ptr` must not equal `len`.
+fn fat_ptr_offsets<P>(ptr: &P, data_ptr: usize, len: usize) -> (usize, usize) {
+	assert_eq!(size_of::<P>(), size_of::<[usize; 2]>());
+	assert_ne!(data_ptr, len);
+	let words: [usize; 2] = unsafe { transmute_copy(ptr) };
+	if words[1] == len {
+		(0, size_of::<usize>())
+	} else {
+		assert_eq!(words[0], len);
+		(size_of::<usize>(), 0)
+	}
+}
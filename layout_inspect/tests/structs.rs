@@ -1,9 +1,10 @@
 use std::mem::{align_of, size_of};
 
 use layout_inspect::{
-	defs::{DefStruct, DefStructField, DefType},
+	defs::{DefRepr, DefStruct, DefStructField, DefType},
 	inspect, Inspect,
 };
+use memoffset::offset_of;
 
 #[test]
 fn struct_single_field() {
@@ -31,6 +32,7 @@ fn struct_single_field() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -53,6 +55,7 @@ fn struct_empty() {
 			fields: vec![],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -72,6 +75,7 @@ fn struct_unit() {
 			fields: vec![],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -131,6 +135,7 @@ fn struct_multiple_fields() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -178,6 +183,7 @@ fn struct_raw_identifier_field_name() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -200,6 +206,7 @@ fn tuple_struct_empty() {
 			fields: vec![],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -228,6 +235,7 @@ fn tuple_struct_single_field() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -285,6 +293,7 @@ fn tuple_struct_multiple_fields() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -320,6 +329,7 @@ fn tuple_struct_with_serde_transparent() {
 			}],
 			tag: None,
 			transparent: true,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -369,6 +379,7 @@ fn struct_generic_one_type_param() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -391,6 +402,7 @@ fn struct_generic_one_type_param() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -415,6 +427,7 @@ fn struct_generic_one_type_param() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -465,6 +478,7 @@ fn struct_generic_two_type_params() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -497,6 +511,7 @@ fn struct_generic_two_type_params() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -532,6 +547,7 @@ fn struct_generic_two_type_params() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 
@@ -565,6 +581,7 @@ fn struct_with_serde_type_rename() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -594,6 +611,7 @@ fn struct_with_serde_tag() {
 			}],
 			tag: Some("type".to_string()),
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -623,6 +641,7 @@ fn struct_with_serde_transparent() {
 			}],
 			tag: None,
 			transparent: true,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -652,6 +671,7 @@ fn struct_with_serde_field_rename() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -702,6 +722,7 @@ fn struct_with_serde_fields_rename_all() {
 			],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -736,6 +757,7 @@ fn struct_with_serde_field_flatten() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -765,6 +787,7 @@ fn struct_with_serde_field_skip() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -799,6 +822,7 @@ fn struct_with_serde_field_rename_and_flatten() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
@@ -828,10 +852,200 @@ fn struct_with_serde_field_default() {
 			}],
 			tag: None,
 			transparent: false,
+			repr: DefRepr::default(),
 		})
 	);
 }
 
+#[test]
+fn struct_repr_c() {
+	#[derive(Inspect)]
+	#[repr(C)]
+	struct Foo {
+		small: u8,
+		big: u32,
+	}
+
+	assert_eq!(
+		inspect::<Foo>()[0],
+		DefType::Struct(DefStruct {
+			name: "Foo".to_string(),
+			ser_name: "Foo".to_string(),
+			size: Some(8),
+			align: Some(4),
+			fields: vec![
+				DefStructField {
+					name: "small".to_string(),
+					ser_name: "small".to_string(),
+					type_id: 1,
+					offset: 0,
+					flatten: false,
+					skip: false,
+				},
+				DefStructField {
+					name: "big".to_string(),
+					ser_name: "big".to_string(),
+					type_id: 2,
+					offset: 4,
+					flatten: false,
+					skip: false,
+				}
+			],
+			tag: None,
+			transparent: false,
+			repr: DefRepr {
+				c: true,
+				..DefRepr::default()
+			},
+		})
+	);
+}
+
+#[test]
+fn struct_repr_transparent() {
+	#[derive(Inspect)]
+	#[repr(transparent)]
+	struct Foo(u64);
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(
+		type_defs[0].to_struct().unwrap().repr,
+		DefRepr {
+			transparent: true,
+			..DefRepr::default()
+		}
+	);
+}
+
+#[test]
+fn struct_repr_align() {
+	#[derive(Inspect)]
+	#[repr(C, align(32))]
+	struct Foo {
+		num: u8,
+	}
+
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_struct().unwrap();
+	assert_eq!(def.size, Some(32));
+	assert_eq!(def.align, Some(32));
+	assert_eq!(
+		def.repr,
+		DefRepr {
+			c: true,
+			align: Some(32),
+			..DefRepr::default()
+		}
+	);
+}
+
+#[test]
+fn struct_repr_multiple_attrs() {
+	#[derive(Inspect)]
+	#[repr(C)]
+	#[repr(packed(2))]
+	struct Foo {
+		num: u8,
+	}
+
+	let type_defs = inspect::<Foo>();
+	assert_eq!(
+		type_defs[0].to_struct().unwrap().repr,
+		DefRepr {
+			c: true,
+			packed: Some(2),
+			..DefRepr::default()
+		}
+	);
+}
+
+#[test]
+fn struct_repr_packed() {
+	#[derive(Inspect)]
+	#[repr(C, packed)]
+	struct Header {
+		kind: u8,
+		len: u32,
+		checksum: u16,
+	}
+
+	let type_defs = inspect::<Header>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Struct(DefStruct {
+			name: "Header".to_string(),
+			ser_name: "Header".to_string(),
+			size: Some(7),
+			align: Some(1),
+			fields: vec![
+				DefStructField {
+					name: "kind".to_string(),
+					ser_name: "kind".to_string(),
+					type_id: 1,
+					offset: 0,
+					flatten: false,
+					skip: false,
+				},
+				DefStructField {
+					name: "len".to_string(),
+					ser_name: "len".to_string(),
+					type_id: 2,
+					offset: 1,
+					flatten: false,
+					skip: false,
+				},
+				DefStructField {
+					name: "checksum".to_string(),
+					ser_name: "checksum".to_string(),
+					type_id: 3,
+					offset: 5,
+					flatten: false,
+					skip: false,
+				}
+			],
+			tag: None,
+			transparent: false,
+			repr: DefRepr {
+				c: true,
+				packed: Some(1),
+				..DefRepr::default()
+			},
+		})
+	);
+}
+
+#[test]
+fn struct_repr_packed_n() {
+	#[derive(Inspect)]
+	#[repr(packed(2))]
+	struct Foo {
+		small: u8,
+		big: u64,
+	}
+
+	#[derive(Inspect)]
+	#[repr(C, packed)]
+	struct Bar(u8, u64, Foo);
+
+	let type_defs = inspect::<Bar>();
+
+	let bar_def = type_defs[0].to_struct().unwrap();
+	assert_eq!(bar_def.size, Some(size_of::<Bar>()));
+	assert_eq!(bar_def.align, Some(1));
+	let offsets: Vec<_> = bar_def.fields.iter().map(|field| field.offset).collect();
+	assert_eq!(offsets, vec![0, 1, 9]);
+
+	let foo_def = type_defs[bar_def.fields[2].type_id as usize]
+		.to_struct()
+		.unwrap();
+	assert_eq!(foo_def.size, Some(size_of::<Foo>()));
+	assert_eq!(foo_def.align, Some(2));
+	assert_eq!(foo_def.fields[0].offset, offset_of!(Foo, small));
+	assert_eq!(foo_def.fields[1].offset, offset_of!(Foo, big));
+	assert_eq!(foo_def.fields[1].offset % 2, 0);
+}
+
 fn get_field_ids(struct_def: &DefType) -> Vec<usize> {
 	struct_def
 		.to_struct()
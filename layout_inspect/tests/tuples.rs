@@ -0,0 +1,138 @@
+use std::mem::{align_of, size_of};
+
+use layout_inspect::{
+	defs::{DefRepr, DefStruct, DefStructField, DefTuple, DefTupleElement, DefType},
+	inspect, Inspect,
+};
+use memoffset::offset_of_tuple;
+
+#[test]
+fn tuple_single_element() {
+	let type_defs = inspect::<(u16,)>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Tuple(DefTuple {
+			name: "(u16,)".to_string(),
+			size: size_of::<u16>(),
+			align: align_of::<u16>(),
+			elements: vec![DefTupleElement {
+				type_id: 1,
+				offset: 0,
+			}],
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u16");
+}
+
+#[test]
+fn tuple_reordered_elements() {
+	type Tup = (u8, u64, u16);
+
+	let type_defs = inspect::<Tup>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Tuple(DefTuple {
+			name: "(u8,u64,u16)".to_string(),
+			size: size_of::<Tup>(),
+			align: align_of::<Tup>(),
+			elements: vec![
+				DefTupleElement {
+					type_id: 1,
+					offset: offset_of_tuple!(Tup, 0),
+				},
+				DefTupleElement {
+					type_id: 2,
+					offset: offset_of_tuple!(Tup, 1),
+				},
+				DefTupleElement {
+					type_id: 3,
+					offset: offset_of_tuple!(Tup, 2),
+				},
+			],
+		})
+	);
+
+	// rustc places largest element first
+	let elements = &type_defs[0].to_tuple().unwrap().elements;
+	assert_eq!(elements[1].offset, 0);
+
+	assert_eq!(type_defs[1].name(), "u8");
+	assert_eq!(type_defs[2].name(), "u64");
+	assert_eq!(type_defs[3].name(), "u16");
+}
+
+#[test]
+fn tuple_repeated_element_type() {
+	let type_defs = inspect::<(u32, u32)>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Tuple(DefTuple {
+			name: "(u32,u32)".to_string(),
+			size: size_of::<u32>() * 2,
+			align: align_of::<u32>(),
+			elements: vec![
+				DefTupleElement {
+					type_id: 1,
+					offset: 0,
+				},
+				DefTupleElement {
+					type_id: 1,
+					offset: 4,
+				},
+			],
+		})
+	);
+
+	assert_eq!(type_defs.len(), 2);
+}
+
+#[test]
+fn tuple_twelve_elements() {
+	type Tup = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+
+	let type_defs = inspect::<Tup>();
+
+	let def = type_defs[0].to_tuple().unwrap();
+	assert_eq!(def.name, "(u8,u8,u8,u8,u8,u8,u8,u8,u8,u8,u8,u8)");
+	assert_eq!(def.size, 12);
+	assert_eq!(def.elements.len(), 12);
+}
+
+#[test]
+fn tuple_in_struct() {
+	#[derive(Inspect)]
+	struct Foo {
+		pair: (u8, String),
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Struct(DefStruct {
+			name: "Foo".to_string(),
+			ser_name: "Foo".to_string(),
+			size: Some(size_of::<Foo>()),
+			align: Some(align_of::<Foo>()),
+			fields: vec![DefStructField {
+				name: "pair".to_string(),
+				ser_name: "pair".to_string(),
+				type_id: 1,
+				offset: 0,
+				flatten: false,
+				skip: false,
+			}],
+			tag: None,
+			transparent: false,
+			repr: DefRepr::default(),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "(u8,String)");
+	assert_eq!(type_defs[2].name(), "u8");
+	assert_eq!(type_defs[3].name(), "String");
+}
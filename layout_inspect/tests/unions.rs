@@ -0,0 +1,166 @@
+use std::mem::{align_of, size_of};
+
+use layout_inspect::{
+	defs::{DefRepr, DefType, DefUnion, DefUnionMember},
+	inspect, Inspect,
+};
+
+#[test]
+fn union_default_repr() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	union Foo {
+		small: u8,
+		big: u64,
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Union(DefUnion {
+			name: "Foo".to_string(),
+			size: size_of::<u64>(),
+			align: align_of::<u64>(),
+			members: vec![
+				DefUnionMember {
+					name: "small".to_string(),
+					type_id: 1,
+				},
+				DefUnionMember {
+					name: "big".to_string(),
+					type_id: 2,
+				},
+			],
+			repr: DefRepr::default(),
+		})
+	);
+
+	assert_eq!(type_defs[1].name(), "u8");
+	assert_eq!(type_defs[2].name(), "u64");
+}
+
+#[test]
+fn union_repr_c() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(C)]
+	union Foo {
+		int: u32,
+		float: f32,
+		bytes: [u8; 4],
+	}
+
+	let type_defs = inspect::<Foo>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Union(DefUnion {
+			name: "Foo".to_string(),
+			size: 4,
+			align: 4,
+			members: vec![
+				DefUnionMember {
+					name: "int".to_string(),
+					type_id: 1,
+				},
+				DefUnionMember {
+					name: "float".to_string(),
+					type_id: 2,
+				},
+				DefUnionMember {
+					name: "bytes".to_string(),
+					type_id: 3,
+				},
+			],
+			repr: DefRepr {
+				c: true,
+				..DefRepr::default()
+			},
+		})
+	);
+
+	assert_eq!(type_defs[3].name(), "[u8; 4]");
+}
+
+#[test]
+fn union_repr_packed_and_align() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(C, packed(2))]
+	union Packed {
+		big: u64,
+		small: u8,
+	}
+
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(align(16))]
+	union Aligned {
+		big: u64,
+	}
+
+	let type_defs = inspect::<Packed>();
+	let def = type_defs[0].to_union().unwrap();
+	assert_eq!(def.size, 8);
+	assert_eq!(def.align, 2);
+	assert_eq!(
+		def.repr,
+		DefRepr {
+			c: true,
+			packed: Some(2),
+			..DefRepr::default()
+		}
+	);
+
+	let type_defs = inspect::<Aligned>();
+	let def = type_defs[0].to_union().unwrap();
+	assert_eq!(def.size, 16);
+	assert_eq!(def.align, 16);
+	assert_eq!(
+		def.repr,
+		DefRepr {
+			align: Some(16),
+			..DefRepr::default()
+		}
+	);
+}
+
+#[test]
+fn union_in_struct() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	#[repr(C)]
+	union Value {
+		int: i64,
+		float: f64,
+	}
+
+	#[derive(Inspect)]
+	#[repr(C)]
+	struct Tagged {
+		tag: u8,
+		value: Value,
+	}
+
+	let type_defs = inspect::<Tagged>();
+
+	let fields = &type_defs[0].to_struct().unwrap().fields;
+	assert_eq!(fields[1].offset, 8);
+	assert_eq!(type_defs[fields[1].type_id as usize].name(), "Value");
+}
+
+#[test]
+fn union_generic() {
+	#[allow(dead_code)]
+	#[derive(Inspect)]
+	union Foo<T: Copy> {
+		value: T,
+		raw: u32,
+	}
+
+	let type_defs = inspect::<Foo<u16>>();
+
+	assert_eq!(type_defs[0].name(), "Foo<u16>");
+	assert_eq!(type_defs[1].name(), "u16");
+}
@@ -0,0 +1,240 @@
+use std::{
+	mem::{align_of_val, size_of, transmute_copy},
+	ptr,
+};
+
+use layout_inspect::{
+	defs::{DefRef, DefRepr, DefStruct, DefStructField, DefType},
+	inspect, Inspect,
+};
+
+/// Get pointer to a zero-length instance of an unsized struct, backed by `buf`
+macro_rules! zero_len_ptr {
+	($type:ty, $buf:expr) => {
+		ptr::slice_from_raw_parts($buf.as_ptr() as *const u8, 0) as *const $type
+	};
+}
+
+/// Get offset of a field, using a zero-length instance backed by `buf`
+macro_rules! field_offset {
+	($type:ty, $buf:expr, $field:tt) => {{
+		let ptr = zero_len_ptr!($type, $buf);
+		unsafe { ptr::addr_of!((*ptr).$field) as *const u8 as usize - ptr as *const u8 as usize }
+	}};
+}
+
+#[test]
+fn unsized_struct_slice() {
+	#[derive(Inspect)]
+	struct Header {
+		len: u32,
+		data: [u8],
+	}
+
+	let type_defs = inspect::<Header>();
+
+	let buf = [0u64; 4];
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Struct(DefStruct {
+			name: "Header".to_string(),
+			ser_name: "Header".to_string(),
+			size: None,
+			align: Some(4),
+			fields: vec![
+				DefStructField {
+					name: "len".to_string(),
+					ser_name: "len".to_string(),
+					type_id: 1,
+					offset: field_offset!(Header, buf, len),
+					flatten: false,
+					skip: false,
+				},
+				DefStructField {
+					name: "data".to_string(),
+					ser_name: "data".to_string(),
+					type_id: 2,
+					offset: field_offset!(Header, buf, data),
+					flatten: false,
+					skip: false,
+				}
+			],
+			tag: None,
+			transparent: false,
+			repr: DefRepr::default(),
+		})
+	);
+	assert_eq!(unsafe { align_of_val(&*zero_len_ptr!(Header, buf)) }, 4);
+
+	assert_eq!(type_defs[1].name(), "u32");
+	assert_eq!(type_defs[2].name(), "[u8]");
+}
+
+#[test]
+fn unsized_struct_str() {
+	#[derive(Inspect)]
+	struct Name {
+		id: u64,
+		flag: bool,
+		text: str,
+	}
+
+	let type_defs = inspect::<Name>();
+	let def = type_defs[0].to_struct().unwrap();
+
+	let buf = [0u64; 4];
+	assert_eq!(def.size, None);
+	assert_eq!(def.align, Some(8));
+	assert_eq!(unsafe { align_of_val(&*zero_len_ptr!(Name, buf)) }, 8);
+	assert_eq!(def.fields[0].offset, field_offset!(Name, buf, id));
+	assert_eq!(def.fields[1].offset, field_offset!(Name, buf, flag));
+	assert_eq!(def.fields[2].offset, field_offset!(Name, buf, text));
+	assert_eq!(type_defs[def.fields[2].type_id as usize].name(), "str");
+}
+
+#[test]
+fn unsized_struct_alignment_from_tail() {
+	#[derive(Inspect)]
+	struct Foo {
+		small: u8,
+		items: [u64],
+	}
+
+	let offsets = get_offsets(&inspect::<Foo>()[0]);
+	let buf = [0u64; 4];
+	assert_eq!(
+		offsets,
+		vec![
+			field_offset!(Foo, buf, small),
+			field_offset!(Foo, buf, items)
+		]
+	);
+	assert_eq!(Foo::align(), Some(8));
+	assert_eq!(Foo::size(), None);
+}
+
+#[test]
+fn unsized_tuple_struct_repr_c() {
+	#[derive(Inspect)]
+	#[repr(C)]
+	struct Foo(u8, u16, [u32]);
+
+	let type_defs = inspect::<Foo>();
+	let def = type_defs[0].to_struct().unwrap();
+
+	assert_eq!(def.size, None);
+	assert_eq!(def.align, Some(4));
+	assert_eq!(
+		def.repr,
+		DefRepr {
+			c: true,
+			..DefRepr::default()
+		}
+	);
+	let offsets: Vec<_> = def.fields.iter().map(|field| field.offset).collect();
+	assert_eq!(offsets, vec![0, 2, 4]);
+}
+
+#[test]
+fn unsized_struct_repr_packed() {
+	#[derive(Inspect)]
+	#[repr(C, packed)]
+	struct Foo {
+		kind: u8,
+		len: u32,
+		data: [u16],
+	}
+
+	let offsets = get_offsets(&inspect::<Foo>()[0]);
+	assert_eq!(offsets, vec![0, 1, 5]);
+	assert_eq!(Foo::align(), Some(1));
+}
+
+#[test]
+fn unsized_struct_repr_align() {
+	#[derive(Inspect)]
+	#[repr(C, align(16))]
+	struct Foo {
+		len: u32,
+		data: [u8],
+	}
+
+	let buf = [0u128; 4];
+	assert_eq!(Foo::align(), Some(16));
+	assert_eq!(unsafe { align_of_val(&*zero_len_ptr!(Foo, buf)) }, 16);
+	let offsets = get_offsets(&inspect::<Foo>()[0]);
+	assert_eq!(offsets, vec![0, 4]);
+}
+
+#[test]
+fn unsized_struct_generic() {
+	#[derive(Inspect)]
+	struct Foo<T> {
+		len: u16,
+		items: [T],
+	}
+
+	let type_defs = inspect::<Foo<u64>>();
+	let def = type_defs[0].to_struct().unwrap();
+
+	let buf = [0u64; 4];
+	assert_eq!(def.name, "Foo<u64>");
+	assert_eq!(def.size, None);
+	assert_eq!(def.align, Some(8));
+	assert_eq!(def.fields[0].offset, field_offset!(Foo<u64>, buf, len));
+	assert_eq!(def.fields[1].offset, field_offset!(Foo<u64>, buf, items));
+	assert_eq!(type_defs[def.fields[1].type_id as usize].name(), "[u64]");
+}
+
+#[test]
+fn unsized_struct_refs() {
+	#[derive(Inspect)]
+	struct Header {
+		len: u32,
+		data: [u8],
+	}
+
+	// Length is in same word as for slice references
+	let array = [0u8; 3];
+	let slice: &[u8] = &array;
+	let words: [usize; 2] = unsafe { transmute_copy(&slice) };
+	let (ptr_offset, len_offset) = if words[1] == array.len() {
+		(0, size_of::<usize>())
+	} else {
+		(size_of::<usize>(), 0)
+	};
+
+	let type_defs = inspect::<&Header>();
+	assert_eq!(
+		type_defs[0],
+		DefType::Ref(DefRef {
+			name: "&Header".to_string(),
+			size: size_of::<&[u8]>(),
+			align: size_of::<usize>(),
+			value_type_id: 1,
+			mutable: false,
+			fat: true,
+			ptr_offset,
+			metadata_offset: Some(len_offset),
+		})
+	);
+	assert_eq!(type_defs[1].name(), "Header");
+
+	let type_defs = inspect::<&mut Header>();
+	assert_eq!(type_defs[0].name(), "&mut Header");
+	assert!(type_defs[0].to_ref().unwrap().mutable);
+
+	let type_defs = inspect::<Box<Header>>();
+	assert_eq!(type_defs[0].name(), "Box<Header>");
+	assert_eq!(type_defs[0].size(), Some(size_of::<&[u8]>()));
+}
+
+fn get_offsets(struct_def: &DefType) -> Vec<usize> {
+	struct_def
+		.to_struct()
+		.unwrap()
+		.fields
+		.iter()
+		.map(|field| field.offset)
+		.collect()
+}
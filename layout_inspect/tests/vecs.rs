@@ -8,6 +8,7 @@ use layout_inspect::{
 #[test]
 fn vec_primitive() {
 	let type_defs = inspect::<Vec<u8>>();
+	let def = type_defs[0].to_vec().unwrap();
 
 	assert_eq!(
 		&type_defs[0],
@@ -16,6 +17,9 @@ fn vec_primitive() {
 			size: size_of::<usize>() * 3,
 			align: align_of::<usize>(),
 			value_type_id: 1,
+			ptr_offset: def.ptr_offset,
+			cap_offset: def.cap_offset,
+			len_offset: def.len_offset,
 		})
 	);
 
@@ -31,6 +35,7 @@ fn vec_struct() {
 	}
 
 	let type_defs = inspect::<Vec<Foo>>();
+	let def = type_defs[0].to_vec().unwrap();
 
 	assert_eq!(
 		&type_defs[0],
@@ -39,8 +44,71 @@ fn vec_struct() {
 			size: size_of::<usize>() * 3,
 			align: align_of::<usize>(),
 			value_type_id: 1,
+			ptr_offset: def.ptr_offset,
+			cap_offset: def.cap_offset,
+			len_offset: def.len_offset,
 		})
 	);
 
 	assert_eq!(type_defs[1].name(), "Foo");
 }
+
+#[test]
+fn vec_offsets() {
+	let type_defs = inspect::<Vec<u32>>();
+	let def = type_defs[0].to_vec().unwrap();
+
+	let mut offsets = [def.ptr_offset, def.cap_offset, def.len_offset];
+	offsets.sort();
+	assert_eq!(offsets, [0, size_of::<usize>(), size_of::<usize>() * 2]);
+
+	// Read pointer, capacity and length from a real `Vec` using the offsets
+	let mut vec = Vec::<u32>::with_capacity(10);
+	vec.extend([1, 2, 3]);
+	let ptr = &vec as *const Vec<u32> as *const u8;
+	let read = |offset: usize| unsafe { (ptr.add(offset) as *const usize).read() };
+	assert_eq!(read(def.ptr_offset), vec.as_ptr() as usize);
+	assert_eq!(read(def.cap_offset), vec.capacity());
+	assert_eq!(read(def.len_offset), 3);
+}
+
+#[test]
+fn vec_zero_sized_type_offsets() {
+	let type_defs = inspect::<Vec<()>>();
+	let def = type_defs[0].to_vec().unwrap();
+
+	let vec = vec![(); 5];
+	let ptr = &vec as *const Vec<()> as *const u8;
+	let read = |offset: usize| unsafe { (ptr.add(offset) as *const usize).read() };
+	assert_eq!(read(def.ptr_offset), vec.as_ptr() as usize);
+	assert_eq!(read(def.len_offset), 5);
+}
+
+// Finding offsets must not allocate space for an element
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn vec_huge_element_offsets() {
+	let type_defs = inspect::<Vec<[u8; 1 << 40]>>();
+	let def = type_defs[0].to_vec().unwrap();
+
+	let type_defs = inspect::<Vec<u8>>();
+	let u8_def = type_defs[0].to_vec().unwrap();
+	assert_eq!(
+		(def.ptr_offset, def.cap_offset, def.len_offset),
+		(u8_def.ptr_offset, u8_def.cap_offset, u8_def.len_offset)
+	);
+}
+
+#[test]
+fn string_offsets() {
+	let type_defs = inspect::<String>();
+	let def = type_defs[0].to_string().unwrap();
+
+	let mut string = String::with_capacity(20);
+	string.push_str("hello");
+	let ptr = &string as *const String as *const u8;
+	let read = |offset: usize| unsafe { (ptr.add(offset) as *const usize).read() };
+	assert_eq!(read(def.ptr_offset), string.as_ptr() as usize);
+	assert_eq!(read(def.cap_offset), string.capacity());
+	assert_eq!(read(def.len_offset), 5);
+}
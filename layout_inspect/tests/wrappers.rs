@@ -0,0 +1,207 @@
+use std::{
+	borrow::Cow,
+	cmp::Reverse,
+	convert::Infallible,
+	mem::{align_of, size_of, ManuallyDrop, MaybeUninit},
+	num::{Saturating, Wrapping},
+	ops::{Range, RangeInclusive},
+	pin::Pin,
+};
+
+use layout_inspect::{
+	defs::{DefMaybeUninit, DefPrimitive, DefPrimitiveKind, DefTransparent, DefType},
+	inspect,
+};
+
+/// Read value of type `V` at `offset` bytes from start of `value`
+fn read_at<T, V: Copy>(value: &T, offset: usize) -> V {
+	assert!(offset + size_of::<V>() <= size_of::<T>());
+	// SAFETY: Checked within bounds. Callers only read initialized fields.
+	unsafe { ((value as *const T as *const u8).add(offset) as *const V).read_unaligned() }
+}
+
+#[test]
+fn cow_str() {
+	let type_defs = inspect::<Cow<str>>();
+	let def = type_defs[0].to_cow().unwrap();
+
+	assert_eq!(def.name, "Cow<str>");
+	assert_eq!(def.size, size_of::<Cow<str>>());
+	assert_eq!(def.align, align_of::<Cow<str>>());
+	assert_eq!(type_defs[def.borrowed_type_id as usize].name(), "&str");
+	assert_eq!(type_defs[def.owned_type_id as usize].name(), "String");
+
+	// Whether `Cow<str>` uses a niche depends on std version.
+	// If it does, check it against real values.
+	assert_eq!(
+		def.niche.is_some(),
+		size_of::<Cow<str>>() == size_of::<String>()
+	);
+	if let Some(niche) = &def.niche {
+		assert_eq!(niche.untagged_variant, "Owned");
+		assert_eq!(niche.values.len(), 1);
+		assert_eq!(niche.values[0].variant, "Borrowed");
+		assert_eq!(niche.size, size_of::<usize>());
+
+		let borrowed: Cow<str> = Cow::Borrowed("abc");
+		assert_eq!(
+			read_at::<_, usize>(&borrowed, niche.offset) as u128,
+			niche.values[0].value
+		);
+		let owned: Cow<str> = Cow::Owned("abc".to_string());
+		assert_ne!(
+			read_at::<_, usize>(&owned, niche.offset) as u128,
+			niche.values[0].value
+		);
+	}
+}
+
+#[test]
+fn cow_slice() {
+	let type_defs = inspect::<Cow<[u16]>>();
+	let def = type_defs[0].to_cow().unwrap();
+
+	assert_eq!(def.name, "Cow<[u16]>");
+	assert_eq!(type_defs[def.borrowed_type_id as usize].name(), "&[u16]");
+	assert_eq!(type_defs[def.owned_type_id as usize].name(), "Vec<u16>");
+}
+
+#[test]
+fn transparent_wrappers() {
+	let type_defs = inspect::<(
+		Pin<Box<u8>>,
+		ManuallyDrop<u16>,
+		Wrapping<u32>,
+		Saturating<i8>,
+		Reverse<u64>,
+	)>();
+
+	let wrappers = type_defs
+		.iter()
+		.filter_map(|def| def.to_transparent())
+		.map(|def| {
+			(
+				&def.name[..],
+				def.size,
+				type_defs[def.value_type_id as usize].name(),
+			)
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(
+		wrappers,
+		[
+			("Pin<Box<u8>>", size_of::<usize>(), "Box<u8>"),
+			("ManuallyDrop<u16>", 2, "u16"),
+			("Wrapping<u32>", 4, "u32"),
+			("Saturating<i8>", 1, "i8"),
+			("Reverse<u64>", 8, "u64"),
+		]
+	);
+}
+
+#[test]
+fn transparent_wrapper_niche() {
+	let type_defs = inspect::<Option<ManuallyDrop<bool>>>();
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::Transparent(DefTransparent {
+			name: "ManuallyDrop<bool>".to_string(),
+			size: 1,
+			align: 1,
+			value_type_id: 2,
+		})
+	);
+	let niche = type_defs[0].to_option().unwrap().niche.as_ref().unwrap();
+	assert_eq!((niche.offset, niche.values[0].value), (0, 2));
+}
+
+#[test]
+fn maybe_uninit() {
+	let type_defs = inspect::<Option<MaybeUninit<bool>>>();
+
+	assert_eq!(
+		&type_defs[1],
+		&DefType::MaybeUninit(DefMaybeUninit {
+			name: "MaybeUninit<bool>".to_string(),
+			size: 1,
+			align: 1,
+			value_type_id: 2,
+		})
+	);
+	// `MaybeUninit` has no niche
+	let def = type_defs[0].to_option().unwrap();
+	assert_eq!(def.size, 2);
+	assert_eq!(def.niche, None);
+}
+
+#[test]
+fn infallible() {
+	let type_defs = inspect::<Infallible>();
+
+	assert_eq!(
+		&type_defs[0],
+		&DefType::Primitive(DefPrimitive {
+			name: "Infallible".to_string(),
+			size: 0,
+			align: 1,
+			kind: DefPrimitiveKind::Never,
+			valid_ranges: vec![],
+		})
+	);
+}
+
+#[test]
+fn range() {
+	let type_defs = inspect::<Range<u32>>();
+	let def = type_defs[0].to_struct().unwrap();
+
+	assert_eq!(def.name, "Range<u32>");
+	assert_eq!(def.ser_name, "Range");
+	assert_eq!(def.size, Some(8));
+	let fields = def
+		.fields
+		.iter()
+		.map(|field| (&field.name[..], field.type_id, field.skip))
+		.collect::<Vec<_>>();
+	assert_eq!(fields, [("start", 1, false), ("end", 1, false)]);
+
+	let range = 5u32..9;
+	assert_eq!(read_at::<_, u32>(&range, def.fields[0].offset), 5);
+	assert_eq!(read_at::<_, u32>(&range, def.fields[1].offset), 9);
+}
+
+#[test]
+fn range_inclusive() {
+	let type_defs = inspect::<RangeInclusive<u16>>();
+	let def = type_defs[0].to_struct().unwrap();
+
+	assert_eq!(def.name, "RangeInclusive<u16>");
+	assert_eq!(def.size, Some(size_of::<RangeInclusive<u16>>()));
+	let fields = def
+		.fields
+		.iter()
+		.map(|field| {
+			(
+				&field.name[..],
+				type_defs[field.type_id as usize].name(),
+				field.skip,
+			)
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(
+		fields,
+		[
+			("start", "u16", false),
+			("end", "u16", false),
+			("exhausted", "bool", true),
+		]
+	);
+
+	let mut range = 3u16..=4;
+	assert_eq!(read_at::<_, u16>(&range, def.fields[0].offset), 3);
+	assert_eq!(read_at::<_, u16>(&range, def.fields[1].offset), 4);
+	assert!(!read_at::<_, bool>(&range, def.fields[2].offset));
+	range.by_ref().for_each(drop);
+	assert!(read_at::<_, bool>(&range, def.fields[2].offset));
+}
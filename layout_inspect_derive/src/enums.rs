@@ -1,24 +1,22 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
-	parse_quote, Attribute, DataEnum, Expr, Fields, FieldsUnnamed, GenericParam, Generics, Ident, Lit,
+	parse_quote, Attribute, DataEnum, Field, Fields, FieldsNamed, FieldsUnnamed, Generics, Ident,
+	Index, Member,
 };
 
 use crate::{
 	attrs::{get_serde_attrs, SerdeAttrs},
+	generics::{add_inspect_bounds, get_type_name},
 	rename::{get_ident_name, get_ser_name},
+	repr::{get_repr_attrs, get_repr_def, ReprAttrs},
 };
 
-// TODO: Support generic enums e.g. `enum Maybe<T> { Some(T), Nothing }`
-// TODO: Should `discriminant` be `i64` not `u64`?
-// TODO: For fieldless enums, use e.g. `Foo::Bar as u64` to get discriminants.
-// Discrimants can be defined as a const expression which we can't parse
-// e.g. `enum X { Y = mem::size_of::<u32>() }`
-// NB Only legal for enums where all variants are fieldless, or `#[repr(u8)]`.
-// That includes e.g. `enum X { Tuple(), Struct{} }`.
-// Also see: https://rust-lang.github.io/rfcs/2363-arbitrary-enum-discriminant.html
-// Maybe possible to obtain discriminant values for field-ful enums
-// if enum is `#[repr(u../i..)]`.
+// Discriminants can be defined as a const expression which we can't evaluate
+// e.g. `enum X { Y = mem::size_of::<u32>() }`, and casting enum to an integer
+// is only legal for fieldless enums.
+// So discriminants are obtained by casting variants of a fieldless enum
+// with same variants, discriminants and `#[repr]` e.g. `Mirror::Y as i128`.
 
 pub fn derive_enum(
 	data: DataEnum,
@@ -26,8 +24,6 @@ pub fn derive_enum(
 	mut generics: Generics,
 	attrs: Vec<Attribute>,
 ) -> TokenStream {
-	let mut next_discriminant: u64 = 0;
-
 	let SerdeAttrs {
 		rename: ser_name,
 		rename_all,
@@ -36,7 +32,17 @@ pub fn derive_enum(
 		untagged,
 		..
 	} = get_serde_attrs(&attrs, "enum");
-	let ser_name = ser_name.unwrap_or_else(|| ident.to_string());
+	let repr_attrs = get_repr_attrs(&attrs);
+	let repr = get_repr_def(&repr_attrs);
+
+	add_inspect_bounds(&mut generics);
+	let name = get_type_name(&ident, &generics);
+
+	let ser_name = if let Some(ser_name) = ser_name {
+		quote! { #ser_name.to_string() }
+	} else {
+		name.clone()
+	};
 
 	let tag = if let Some(tag) = tag {
 		if let Some(content) = content {
@@ -50,68 +56,67 @@ pub fn derive_enum(
 		quote! { DefEnumTag::None }
 	};
 
-	let variant_defs: Vec<_> = data
+	// Fieldless enum with same variants, discriminants and `#[repr]`.
+	// Its size is the minimum size of the tag.
+	let discriminants_enum = get_discriminants_enum(&data, &repr_attrs);
+
+	let variant_offsets = get_variant_offsets(&data);
+
+	let (variant_defs, field_layouts): (Vec<_>, Vec<_>) = data
 		.variants
 		.into_iter()
-		.map(|variant| {
+		.enumerate()
+		.map(|(variant_index, variant)| {
 			let name = get_ident_name(&variant.ident);
 
-			let (ser_value, value_type_id) = match variant.fields {
-				Fields::Unit => {
-					let SerdeAttrs {
-						rename: ser_value, ..
-					} = get_serde_attrs(&variant.attrs, "enum variant");
+			let field_types = variant.fields.iter().map(|field| &field.ty);
+			let field_layouts = quote! {
+				vec![#((mem::size_of::<#field_types>(), mem::align_of::<#field_types>())),*]
+			};
 
+			let SerdeAttrs {
+				rename: ser_value,
+				rename_all: fields_rename_all,
+				..
+			} = get_serde_attrs(&variant.attrs, "enum variant");
+
+			let (ser_value, payload) = match variant.fields {
+				Fields::Unit => {
 					// Get variant value, optionally applying `rename_all` transform.
 					// `serde(rename)` on variant takes precedence.
 					let ser_value = get_ser_name(&name, &ser_value, &rename_all);
 					let ser_value = quote! { Some(#ser_value.to_string()) };
-					let value_type_id = quote! { None };
-					(ser_value, value_type_id)
+					(ser_value, quote! { DefEnumVariantPayload::Unit })
 				}
-				Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-					assert!(unnamed.len() == 1);
-					let ty = &unnamed.first().unwrap().ty;
-					let ser_value = quote! { None };
-					let value_type_id = quote! { Some(collector.collect::<#ty>()) };
-					(ser_value, value_type_id)
+				Fields::Unnamed(fields) => {
+					let fields =
+						get_unnamed_field_defs(&variant.ident, variant_index, fields, &fields_rename_all);
+					let payload = quote! { DefEnumVariantPayload::Tuple(vec![#(#fields),*]) };
+					(quote! { None }, payload)
 				}
-				Fields::Named(_) => todo!(),
-			};
-
-			let discriminant = match variant.discriminant {
-				Some(discriminant) => {
-					match discriminant.1 {
-						Expr::Lit(expr_lit) => {
-							match expr_lit.lit {
-								Lit::Int(int) => int.base10_parse::<u64>().unwrap(),
-								_ => todo!(),
-							}
-						}
-						_ => todo!(),
-					}
+				Fields::Named(fields) => {
+					let fields =
+						get_named_field_defs(&variant.ident, variant_index, fields, &fields_rename_all);
+					let payload = quote! { DefEnumVariantPayload::Struct(vec![#(#fields),*]) };
+					(quote! { None }, payload)
 				}
-				None => next_discriminant,
 			};
-			next_discriminant = discriminant + 1;
 
-			quote! {
+			let variant_ident = &variant.ident;
+			let discriminant = quote! { __InspectDiscriminants::#variant_ident as i128 };
+
+			let variant_def = quote! {
 				DefEnumVariant {
 					name: #name.to_string(),
 					discriminant: #discriminant,
 					ser_value: #ser_value,
-					value_type_id: #value_type_id,
+					payload: #payload,
+					payload_offset: None,
 				}
-			}
+			};
+			(variant_def, field_layouts)
 		})
-		.collect();
-
-	// Add bound `Inspect` to type params
-	for param in &mut generics.params {
-		if let GenericParam::Type(ref mut type_param) = *param {
-			type_param.bounds.push(parse_quote!(Inspect));
-		}
-	}
+		.unzip();
 
 	// Return `impl` code
 	let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
@@ -125,14 +130,20 @@ pub fn derive_enum(
 				stringify, vec,
 			};
 			use ::layout_inspect::{
-				defs::{DefEnum, DefEnumTag, DefEnumVariant, DefType},
+				__private::complete_enum_layout,
+				defs::{
+					DefEnum, DefEnumTag, DefEnumVariant, DefEnumVariantField, DefEnumVariantPayload,
+					DefRepr, DefType,
+				},
 				Inspect, TypesCollector,
 			};
 
+			#discriminants_enum
+
 			#[automatically_derived]
 			impl #impl_generics Inspect for #ident #type_generics #where_clause {
 				fn name() -> String {
-					stringify!(#ident).to_string()
+					#name
 				}
 
 				fn size() -> Option<usize> {
@@ -144,16 +155,219 @@ pub fn derive_enum(
 				}
 
 				fn def(collector: &mut TypesCollector) -> DefType {
-					DefType::Enum(DefEnum {
+					#variant_offsets
+					let mut def = DefEnum {
 						name: <Self as Inspect>::name(),
-						ser_name: #ser_name.to_string(),
+						ser_name: #ser_name,
 						size: <Self as Inspect>::size().unwrap(),
 						align: <Self as Inspect>::align().unwrap(),
 						variants: vec![#(#variant_defs),*],
 						tag: #tag,
-					})
+						repr: #repr,
+						tag_layout: None,
+						niche: None,
+					};
+					complete_enum_layout(
+						&mut def,
+						mem::size_of::<__InspectDiscriminants>(),
+						&[#(#field_layouts),*],
+						collector,
+					);
+					DefType::Enum(def)
 				}
 			}
 		};
 	}
 }
+
+/// Create code for a fieldless enum with same variants, discriminants and
+/// `#[repr]` as the enum being derived.
+/// Only the parts of `#[repr]` which affect size of tag are copied.
+/// `#[repr(C, u8)]` is not legal on a fieldless enum, so int takes precedence.
+fn get_discriminants_enum(data: &DataEnum, repr: &ReprAttrs) -> TokenStream {
+	let repr_attr = if data.variants.is_empty() {
+		quote! {}
+	} else if let Some(int) = &repr.int {
+		let int = Ident::new(int, Span::call_site());
+		quote! { #[repr(#int)] }
+	} else if repr.c {
+		quote! { #[repr(C)] }
+	} else {
+		quote! {}
+	};
+	let variants = data.variants.iter().map(|variant| {
+		let ident = &variant.ident;
+		match &variant.discriminant {
+			Some((_, discriminant)) => quote! { #ident = #discriminant },
+			None => quote! { #ident },
+		}
+	});
+
+	quote! {
+		#[allow(dead_code, non_camel_case_types, clippy::enum_variant_names)]
+		#repr_attr
+		enum __InspectDiscriminants {
+			#(#variants),*
+		}
+	}
+}
+
+fn get_named_field_defs(
+	variant_ident: &Ident,
+	variant_index: usize,
+	fields: FieldsNamed,
+	rename_all: &Option<String>,
+) -> Vec<TokenStream> {
+	fields
+		.named
+		.iter()
+		.enumerate()
+		.map(|(index, field)| {
+			let ident = field.ident.as_ref().expect("Missing field name");
+			let member: Member = parse_quote!(#ident);
+			let name = get_ident_name(ident);
+			let offset = get_variant_field_offset(variant_ident, variant_index, &member, index);
+			get_field_def(field, offset, name, rename_all)
+		})
+		.collect()
+}
+
+fn get_unnamed_field_defs(
+	variant_ident: &Ident,
+	variant_index: usize,
+	fields: FieldsUnnamed,
+	rename_all: &Option<String>,
+) -> Vec<TokenStream> {
+	fields
+		.unnamed
+		.iter()
+		.enumerate()
+		.map(|(index, field)| {
+			let member = Member::Unnamed(Index {
+				index: index as u32,
+				span: Span::call_site(),
+			});
+			let offset = get_variant_field_offset(variant_ident, variant_index, &member, index);
+			get_field_def(field, offset, index.to_string(), rename_all)
+		})
+		.collect()
+}
+
+fn get_field_def(
+	field: &Field,
+	offset: TokenStream,
+	name: String,
+	rename_all: &Option<String>,
+) -> TokenStream {
+	let SerdeAttrs {
+		rename: ser_name,
+		flatten,
+		skip,
+		..
+	} = get_serde_attrs(&field.attrs, "enum variant field");
+
+	// Get field name, optionally applying variant's `rename_all` transform.
+	// `serde(rename)` on field takes precedence.
+	let ser_name = get_ser_name(&name, &ser_name, rename_all);
+
+	let ty = &field.ty;
+	quote! {
+		DefEnumVariantField {
+			name: #name.to_string(),
+			ser_name: #ser_name.to_string(),
+			type_id: collector.collect::<#ty>(),
+			offset: #offset,
+			flatten: #flatten,
+			skip: #skip,
+		}
+	}
+}
+
+/// Get offset of a field within an enum variant.
+/// `offset_of!` on enum variants is unstable, so only used with
+/// `offset_of_enum` feature. Crates using derive must enable `offset_of_enum`
+/// and `offset_of_nested` compiler features.
+#[cfg(feature = "offset_of_enum")]
+fn get_variant_field_offset(
+	variant_ident: &Ident,
+	_variant_index: usize,
+	member: &Member,
+	_field_index: usize,
+) -> TokenStream {
+	quote! { Some(mem::offset_of!(Self, #variant_ident.#member)) }
+}
+
+/// Get offset of a field within an enum variant.
+/// Without `offset_of_enum` feature, offsets are found by constructing each
+/// variant, in code from `get_variant_offsets`.
+#[cfg(not(feature = "offset_of_enum"))]
+fn get_variant_field_offset(
+	_variant_ident: &Ident,
+	variant_index: usize,
+	_member: &Member,
+	field_index: usize,
+) -> TokenStream {
+	quote! { variant_offsets[#variant_index].as_ref().map(|offsets| offsets[#field_index]) }
+}
+
+#[cfg(feature = "offset_of_enum")]
+fn get_variant_offsets(_data: &DataEnum) -> TokenStream {
+	quote! {}
+}
+
+/// Create code to get offsets of fields of each variant, by constructing the
+/// variant with its fields' `Default` values and matching on it.
+/// Offsets of a variant's fields are `None` if any field type does not
+/// implement `Default`, or is a type param.
+#[cfg(not(feature = "offset_of_enum"))]
+fn get_variant_offsets(data: &DataEnum) -> TokenStream {
+	let probes = data.variants.iter().map(|variant| {
+		if variant.fields.is_empty() {
+			return quote! { None };
+		}
+
+		let ident = &variant.ident;
+		let members: Vec<Member> = variant
+			.fields
+			.iter()
+			.enumerate()
+			.map(|(index, field)| {
+				match &field.ident {
+					Some(ident) => parse_quote!(#ident),
+					None => {
+						Member::Unnamed(Index {
+							index: index as u32,
+							span: Span::call_site(),
+						})
+					}
+				}
+			})
+			.collect();
+		let bindings: Vec<Ident> = (0..members.len())
+			.map(|index| Ident::new(&format!("field{}", index), Span::call_site()))
+			.collect();
+		let field_types = variant.fields.iter().map(|field| &field.ty);
+
+		quote! {
+			match (#((&DefaultProbe::<#field_types>::new()).probe(),)*) {
+				(#(Some(#bindings),)*) => {
+					let value = Self::#ident { #(#members: #bindings),* };
+					let base = &value as *const Self as usize;
+					match &value {
+						Self::#ident { #(#members: #bindings),* } => {
+							Some(vec![#(#bindings as *const _ as usize - base),*])
+						}
+						#[allow(unreachable_patterns)]
+						_ => None,
+					}
+				}
+				_ => None,
+			}
+		}
+	});
+
+	quote! {
+		use ::layout_inspect::__private::{DefaultProbe, ProbeDefault, ProbeNone};
+		let variant_offsets: &[Option<vec::Vec<usize>>] = &[#(#probes),*];
+	}
+}
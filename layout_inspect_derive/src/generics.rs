@@ -0,0 +1,42 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, GenericParam, Generics, Ident};
+
+/// Add bound `Inspect` to type params
+pub fn add_inspect_bounds(generics: &mut Generics) {
+	for param in &mut generics.params {
+		if let GenericParam::Type(ref mut type_param) = *param {
+			type_param.bounds.push(parse_quote!(Inspect));
+		}
+	}
+}
+
+/// Create code for name of struct / enum.
+/// Type params are included e.g. `Foo<u8,String>`.
+pub fn get_type_name(ident: &Ident, generics: &Generics) -> TokenStream {
+	let sub_types: Vec<TokenStream> = generics
+		.params
+		.iter()
+		.filter_map(|param| {
+			match param {
+				GenericParam::Type(param) => {
+					let ident = &param.ident;
+					Some(quote! {&<#ident as Inspect>::name() +})
+				}
+				_ => None,
+			}
+		})
+		.collect();
+
+	let sub_types_str = if !sub_types.is_empty() {
+		let mut sub_types: Vec<_> = sub_types
+			.into_iter()
+			.flat_map(|sub_type| [sub_type, quote! {"," +}])
+			.collect();
+		sub_types.pop();
+		quote! {+ "<" + #(#sub_types)* ">"}
+	} else {
+		quote! {}
+	};
+	quote! { stringify!(#ident).to_string() #sub_types_str }
+}
@@ -4,8 +4,12 @@ mod structs;
 use structs::derive_struct;
 mod enums;
 use enums::derive_enum;
+mod unions;
+use unions::derive_union;
 mod attrs;
+mod generics;
 mod rename;
+mod repr;
 
 #[proc_macro_derive(Inspect, attributes(serde))]
 pub fn inspect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -17,6 +21,6 @@ fn inspect_impl(input: DeriveInput) -> proc_macro2::TokenStream {
 	match input.data {
 		Data::Struct(data) => derive_struct(data, input.ident, input.generics, input.attrs),
 		Data::Enum(data) => derive_enum(data, input.ident, input.generics, input.attrs),
-		Data::Union(_data) => todo!("Deriving `Inspect` on Unions not supported"),
+		Data::Union(data) => derive_union(data, input.ident, input.generics, input.attrs),
 	}
 }
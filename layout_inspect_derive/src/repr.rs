@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{AttrStyle, Attribute, Lit, Meta, NestedMeta};
+
+#[derive(Default)]
+pub struct ReprAttrs {
+	pub c: bool,
+	pub transparent: bool,
+	pub packed: Option<usize>,
+	pub align: Option<usize>,
+	pub int: Option<String>,
+}
+
+const INT_REPRS: [&str; 12] = [
+	"u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Parse `#[repr()]` attributes for a struct / enum / union
+pub fn get_repr_attrs(attrs: &Vec<Attribute>) -> ReprAttrs {
+	let mut out = ReprAttrs::default();
+	for attr in attrs {
+		if attr.style != AttrStyle::Outer || !attr.path.is_ident("repr") {
+			continue;
+		}
+
+		let meta = attr.parse_meta().unwrap();
+		let list = if let Meta::List(list) = meta {
+			list
+		} else {
+			continue;
+		};
+
+		for item in list.nested {
+			let meta = if let NestedMeta::Meta(meta) = item {
+				meta
+			} else {
+				continue;
+			};
+
+			match meta {
+				Meta::Path(path) => {
+					let name = if let Some(name) = path.get_ident() {
+						name.to_string()
+					} else {
+						continue;
+					};
+
+					match &*name {
+						"C" => out.c = true,
+						"transparent" => out.transparent = true,
+						// `#[repr(packed)]` is equivalent to `#[repr(packed(1))]`
+						"packed" => out.packed = Some(1),
+						name if INT_REPRS.contains(&name) => out.int = Some(name.to_string()),
+						_ => {}
+					}
+				}
+				Meta::List(list) => {
+					let name = if let Some(name) = list.path.get_ident() {
+						name.to_string()
+					} else {
+						continue;
+					};
+
+					let value = match list.nested.first() {
+						Some(NestedMeta::Lit(Lit::Int(int))) => int.base10_parse::<usize>().unwrap(),
+						_ => continue,
+					};
+
+					match &*name {
+						"packed" => out.packed = Some(value),
+						"align" => out.align = Some(value),
+						_ => {}
+					}
+				}
+				_ => {}
+			}
+		}
+	}
+	out
+}
+
+/// Create code for `DefRepr`
+pub fn get_repr_def(repr: &ReprAttrs) -> TokenStream {
+	let ReprAttrs {
+		c,
+		transparent,
+		packed,
+		align,
+		int,
+	} = repr;
+	let packed = option_to_tokens(packed.map(|packed| quote! { #packed }));
+	let align = option_to_tokens(align.map(|align| quote! { #align }));
+	let int = option_to_tokens(int.as_ref().map(|int| quote! { #int.to_string() }));
+	quote! {
+		DefRepr {
+			c: #c,
+			transparent: #transparent,
+			packed: #packed,
+			align: #align,
+			int: #int,
+		}
+	}
+}
+
+fn option_to_tokens(value: Option<TokenStream>) -> TokenStream {
+	value.map_or_else(|| quote! { None }, |value| quote! { Some(#value) })
+}
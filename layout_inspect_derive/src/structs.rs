@@ -1,13 +1,15 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
-	parse_quote, Attribute, DataStruct, Field, Fields, FieldsNamed, FieldsUnnamed, GenericParam,
-	Generics, Ident, Index, Member,
+	parse_quote, Attribute, DataStruct, Field, Fields, FieldsNamed, FieldsUnnamed, Generics, Ident,
+	Index, Lifetime, LifetimeDef, Member, Type,
 };
 
 use crate::{
 	attrs::{get_serde_attrs, SerdeAttrs},
+	generics::{add_inspect_bounds, get_type_name},
 	rename::{get_ident_name, get_ser_name},
+	repr::{get_repr_attrs, get_repr_def},
 };
 
 pub fn derive_struct(
@@ -23,47 +25,22 @@ pub fn derive_struct(
 		transparent,
 		..
 	} = get_serde_attrs(&attrs, "struct");
+	let repr_attrs = get_repr_attrs(&attrs);
+	let repr = get_repr_def(&repr_attrs);
+
+	// Structs with a `str` or slice as last field are unsized
+	let field_types: Vec<Type> = data.fields.iter().map(|field| field.ty.clone()).collect();
+	let is_unsized = field_types.last().map_or(false, is_unsized_type);
 
 	// Get field definitions
 	let field_defs: Vec<TokenStream> = match data.fields {
-		Fields::Named(fields) => get_named_field_defs(fields, &rename_all),
-		Fields::Unnamed(fields) => get_unnamed_field_defs(fields, &rename_all),
+		Fields::Named(fields) => get_named_field_defs(fields, &rename_all, is_unsized),
+		Fields::Unnamed(fields) => get_unnamed_field_defs(fields, &rename_all, is_unsized),
 		Fields::Unit => vec![],
 	};
 
-	// Add bound `Inspect` to type params
-	for param in &mut generics.params {
-		if let GenericParam::Type(ref mut type_param) = *param {
-			type_param.bounds.push(parse_quote!(Inspect));
-		}
-	}
-
-	// Create code for name
-	let sub_types: Vec<TokenStream> = generics
-		.params
-		.iter()
-		.filter_map(|param| {
-			match param {
-				GenericParam::Type(param) => {
-					let ident = &param.ident;
-					Some(quote! {&<#ident as Inspect>::name() +})
-				}
-				_ => None,
-			}
-		})
-		.collect();
-
-	let sub_types_str = if !sub_types.is_empty() {
-		let mut sub_types: Vec<_> = sub_types
-			.into_iter()
-			.flat_map(|sub_type| [sub_type, quote! {"," +}])
-			.collect();
-		sub_types.pop();
-		quote! {+ "<" + #(#sub_types)* ">"}
-	} else {
-		quote! {}
-	};
-	let name = quote! { stringify!(#ident).to_string() #sub_types_str };
+	add_inspect_bounds(&mut generics);
+	let name = get_type_name(&ident, &generics);
 
 	let ser_name = if let Some(ser_name) = ser_name {
 		quote! { #ser_name.to_string() }
@@ -76,19 +53,71 @@ pub fn derive_struct(
 	// Return `impl` code
 	let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
+	let (size, align, probe, ref_impls, imports) = if is_unsized {
+		// Alignment of an unsized struct is maximum alignment of its fields,
+		// adjusted by `#[repr(packed)]` / `#[repr(align)]`
+		let packed = repr_attrs
+			.packed
+			.map(|packed| quote! { let align = align.min(#packed); });
+		let min_align = repr_attrs
+			.align
+			.map(|min_align| quote! { let align = align.max(#min_align); });
+		let align = quote! {
+			let mut align = 1;
+			#(align = align.max(<#field_types as Inspect>::align()?);)*
+			#packed
+			#min_align
+			Some(align)
+		};
+
+		// Field offsets are measured from pointer to a zero-length instance.
+		// Allocation is large enough for sized fields with maximum padding
+		// between them.
+		let sized_types = &field_types[..field_types.len() - 1];
+		let field_count = field_types.len();
+		let probe = quote! {
+			let align = <Self as Inspect>::align().unwrap();
+			let alloc = UnsizedAlloc::new(
+				0 #(+ mem::size_of::<#sized_types>())* + align * #field_count,
+				align,
+			);
+			let ptr = ptr::slice_from_raw_parts(alloc.as_ptr(), 0) as *const Self;
+		};
+
+		let ref_impls = get_ref_impls(&ident, &generics);
+
+		let imports = quote! {
+			use ::std::ptr;
+			use ::layout_inspect::{
+				__private::{pointer_offsets, UnsizedAlloc},
+				defs::DefRef,
+			};
+		};
+
+		(quote! { None }, align, probe, ref_impls, imports)
+	} else {
+		(
+			quote! { Some(mem::size_of::<Self>()) },
+			quote! { Some(mem::align_of::<Self>()) },
+			quote! {},
+			quote! {},
+			quote! { use ::layout_inspect::__private::memoffset::offset_of; },
+		)
+	};
+
 	quote! {
 		const _: () = {
 			use ::std::{
 				mem,
-				option::Option::{self, Some},
+				option::Option::{self, None, Some},
 				string::String,
 				stringify, vec,
 			};
 			use ::layout_inspect::{
-				__private::memoffset::offset_of,
-				defs::{DefStruct, DefStructField, DefType},
+				defs::{DefRepr, DefStruct, DefStructField, DefType},
 				Inspect, TypesCollector,
 			};
+			#imports
 
 			#[automatically_derived]
 			impl #impl_generics Inspect for #ident #type_generics #where_clause {
@@ -96,17 +125,16 @@ pub fn derive_struct(
 					#name
 				}
 
-				// TODO: Allow deriving for unsized types
-				// TODO: Deduce alignment for unsized types where possible e.g. `struct X { n: u64, s: str }`
 				fn size() -> Option<usize> {
-					Some(mem::size_of::<Self>())
+					#size
 				}
 
 				fn align() -> Option<usize> {
-					Some(mem::align_of::<Self>())
+					#align
 				}
 
 				fn def(collector: &mut TypesCollector) -> DefType {
+					#probe
 					DefType::Struct(DefStruct {
 						name: <Self as Inspect>::name(),
 						ser_name: #ser_name,
@@ -115,14 +143,84 @@ pub fn derive_struct(
 						fields: vec![#(#field_defs),*],
 						tag: #tag,
 						transparent: #transparent,
+						repr: #repr,
 					})
 				}
 			}
+
+			#ref_impls
 		};
 	}
 }
 
-fn get_named_field_defs(fields: FieldsNamed, rename_all: &Option<String>) -> Vec<TokenStream> {
+/// Determine if type is `str` or a slice, which makes a struct unsized if it's
+/// the last field.
+fn is_unsized_type(ty: &Type) -> bool {
+	match ty {
+		Type::Slice(_) => true,
+		Type::Path(path) => path.qself.is_none() && path.path.is_ident("str"),
+		Type::Group(group) => is_unsized_type(&group.elem),
+		Type::Paren(paren) => is_unsized_type(&paren.elem),
+		_ => false,
+	}
+}
+
+/// Get `impl`s for `&T` and `&mut T` where `T` is an unsized struct.
+/// Blanket `impl`s for references only cover sized types.
+fn get_ref_impls(ident: &Ident, generics: &Generics) -> TokenStream {
+	let lifetime = Lifetime::new("'__inspect", Span::call_site());
+	let mut ref_generics = generics.clone();
+	ref_generics
+		.params
+		.insert(0, LifetimeDef::new(lifetime.clone()).into());
+	let (impl_generics, _, where_clause) = ref_generics.split_for_impl();
+	let (_, type_generics, _) = generics.split_for_impl();
+
+	let impls = [(quote! {}, false), (quote! { mut }, true)]
+		.into_iter()
+		.map(|(mut_token, mutable)| {
+			let prefix_str = if mutable { "&mut " } else { "&" };
+			quote! {
+				#[automatically_derived]
+				impl #impl_generics Inspect for & #lifetime #mut_token #ident #type_generics #where_clause {
+					fn name() -> String {
+						#prefix_str.to_string() + &<#ident #type_generics as Inspect>::name()
+					}
+
+					fn size() -> Option<usize> {
+						Some(mem::size_of::<Self>())
+					}
+
+					fn align() -> Option<usize> {
+						Some(mem::align_of::<Self>())
+					}
+
+					fn def(collector: &mut TypesCollector) -> DefType {
+						let value_type_id = collector.collect::<#ident #type_generics>();
+						let (ptr_offset, metadata_offset) =
+							pointer_offsets(mem::size_of::<Self>(), value_type_id, collector);
+						DefType::Ref(DefRef {
+							name: <Self as Inspect>::name(),
+							size: <Self as Inspect>::size().unwrap(),
+							align: <Self as Inspect>::align().unwrap(),
+							value_type_id,
+							mutable: #mutable,
+							fat: true,
+							ptr_offset,
+							metadata_offset,
+						})
+					}
+				}
+			}
+		});
+	quote! { #(#impls)* }
+}
+
+fn get_named_field_defs(
+	fields: FieldsNamed,
+	rename_all: &Option<String>,
+	is_unsized: bool,
+) -> Vec<TokenStream> {
 	fields
 		.named
 		.iter()
@@ -130,12 +228,16 @@ fn get_named_field_defs(fields: FieldsNamed, rename_all: &Option<String>) -> Vec
 			let ident = field.ident.as_ref().expect("Missing field name");
 			let member: Member = parse_quote!(#ident);
 			let name = get_ident_name(ident);
-			get_field_def(field, member, name, rename_all)
+			get_field_def(field, member, name, rename_all, is_unsized)
 		})
 		.collect()
 }
 
-fn get_unnamed_field_defs(fields: FieldsUnnamed, rename_all: &Option<String>) -> Vec<TokenStream> {
+fn get_unnamed_field_defs(
+	fields: FieldsUnnamed,
+	rename_all: &Option<String>,
+	is_unsized: bool,
+) -> Vec<TokenStream> {
 	fields
 		.unnamed
 		.iter()
@@ -145,7 +247,7 @@ fn get_unnamed_field_defs(fields: FieldsUnnamed, rename_all: &Option<String>) ->
 				index: index as u32,
 				span: Span::call_site(),
 			});
-			get_field_def(field, member, index.to_string(), rename_all)
+			get_field_def(field, member, index.to_string(), rename_all, is_unsized)
 		})
 		.collect()
 }
@@ -155,6 +257,7 @@ fn get_field_def(
 	member: Member,
 	name: String,
 	rename_all: &Option<String>,
+	is_unsized: bool,
 ) -> TokenStream {
 	let SerdeAttrs {
 		rename: ser_name,
@@ -167,13 +270,24 @@ fn get_field_def(
 	// `serde(rename)` on field takes precedence.
 	let ser_name = get_ser_name(&name, &ser_name, rename_all);
 
+	// `memoffset::offset_of!` uses `ptr::addr_of!`, so does not create references
+	// to fields. This makes it sound for fields of `#[repr(packed)]` structs.
+	// It requires `Self: Sized`, so for unsized structs `ptr::addr_of!` is used
+	// directly on pointer created in `def()`.
+	let offset = if is_unsized {
+		quote! {
+			unsafe { ptr::addr_of!((*ptr).#member) as *const u8 as usize - ptr as *const u8 as usize }
+		}
+	} else {
+		quote! { offset_of!(Self, #member) }
+	};
 	let ty = &field.ty;
 	quote! {
 		DefStructField {
 			name: #name.to_string(),
 			ser_name: #ser_name.to_string(),
 			type_id: collector.collect::<#ty>(),
-			offset: offset_of!(Self, #member),
+			offset: #offset,
 			flatten: #flatten,
 			skip: #skip,
 		}
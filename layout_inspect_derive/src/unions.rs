@@ -0,0 +1,83 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, DataUnion, Generics, Ident};
+
+use crate::{
+	generics::{add_inspect_bounds, get_type_name},
+	rename::get_ident_name,
+	repr::{get_repr_attrs, get_repr_def},
+};
+
+pub fn derive_union(
+	data: DataUnion,
+	ident: Ident,
+	mut generics: Generics,
+	attrs: Vec<Attribute>,
+) -> TokenStream {
+	let repr = get_repr_def(&get_repr_attrs(&attrs));
+
+	// Get member definitions.
+	// All members are at offset 0, so no need to record offsets.
+	let member_defs: Vec<TokenStream> = data
+		.fields
+		.named
+		.iter()
+		.map(|field| {
+			let ident = field.ident.as_ref().expect("Missing field name");
+			let name = get_ident_name(ident);
+			let ty = &field.ty;
+			quote! {
+				DefUnionMember {
+					name: #name.to_string(),
+					type_id: collector.collect::<#ty>(),
+				}
+			}
+		})
+		.collect();
+
+	add_inspect_bounds(&mut generics);
+	let name = get_type_name(&ident, &generics);
+
+	// Return `impl` code
+	let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+	quote! {
+		const _: () = {
+			use ::std::{
+				mem,
+				option::Option::{self, None, Some},
+				string::String,
+				stringify, vec,
+			};
+			use ::layout_inspect::{
+				defs::{DefRepr, DefType, DefUnion, DefUnionMember},
+				Inspect, TypesCollector,
+			};
+
+			#[automatically_derived]
+			impl #impl_generics Inspect for #ident #type_generics #where_clause {
+				fn name() -> String {
+					#name
+				}
+
+				fn size() -> Option<usize> {
+					Some(mem::size_of::<Self>())
+				}
+
+				fn align() -> Option<usize> {
+					Some(mem::align_of::<Self>())
+				}
+
+				fn def(collector: &mut TypesCollector) -> DefType {
+					DefType::Union(DefUnion {
+						name: <Self as Inspect>::name(),
+						size: <Self as Inspect>::size().unwrap(),
+						align: <Self as Inspect>::align().unwrap(),
+						members: vec![#(#member_defs),*],
+						repr: #repr,
+					})
+				}
+			}
+		};
+	}
+}